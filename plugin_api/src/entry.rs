@@ -0,0 +1,142 @@
+use std::any::Any;
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::{Mutex, PoisonError};
+
+use re_set_lib::utils::plugin::{PluginCapabilities, PluginImplementation, SidebarInfo};
+
+/// Exported by [`frontend_entries!`](crate::frontend_entries), ReSet calls it after every
+/// entry point to find out whether the entry point panicked.
+pub const PLUGIN_PANIC_SYMBOL: &[u8] = b"frontend_take_panic";
+
+/// Returns the message of the last panic the plugin caught, or null if there was none.
+/// The message stays valid until the next call.
+pub type PanicTaker = unsafe extern "C" fn() -> *const c_char;
+
+// every plugin links its own copy of this crate and therefore has its own messages
+static CAUGHT_PANIC: Mutex<Option<CString>> = Mutex::new(None);
+static TAKEN_PANIC: Mutex<Option<CString>> = Mutex::new(None);
+
+/// Returned by an entry point whose body panicked, ReSet discards it.
+pub trait PanicFallback {
+    fn fallback() -> Self;
+}
+
+impl PanicFallback for () {
+    fn fallback() -> Self {}
+}
+
+impl PanicFallback for String {
+    fn fallback() -> Self {
+        String::new()
+    }
+}
+
+impl<T> PanicFallback for Vec<T> {
+    fn fallback() -> Self {
+        Vec::new()
+    }
+}
+
+impl<T> PanicFallback for Option<T> {
+    fn fallback() -> Self {
+        None
+    }
+}
+
+impl PanicFallback for *const c_char {
+    fn fallback() -> Self {
+        ptr::null()
+    }
+}
+
+impl PanicFallback for PluginCapabilities {
+    fn fallback() -> Self {
+        PluginCapabilities::new(Vec::new(), false, PluginImplementation::Frontend)
+    }
+}
+
+impl<T> PanicFallback for (SidebarInfo, Vec<T>) {
+    fn fallback() -> Self {
+        let sidebar_info = SidebarInfo {
+            name: "",
+            icon_name: "",
+            parent: None,
+        };
+        (sidebar_info, Vec::new())
+    }
+}
+
+/// Runs the body of an entry point. A panic may not unwind into ReSet, which links its own std
+/// and would abort on the foreign exception, so it is caught here and kept for ReSet.
+pub fn catch_panic<R: PanicFallback>(entry: impl FnOnce() -> R) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(entry)) {
+        Ok(result) => result,
+        Err(payload) => {
+            // a message with a nul byte is cut off there
+            let message = panic_message(payload);
+            let message = message.split('\0').next().unwrap_or_default();
+            *CAUGHT_PANIC.lock().unwrap_or_else(PoisonError::into_inner) =
+                CString::new(message).ok();
+            R::fallback()
+        }
+    }
+}
+
+/// The [`PanicTaker`] of the plugin, exported by [`frontend_entries!`](crate::frontend_entries).
+pub extern "C" fn take_panic() -> *const c_char {
+    let caught = CAUGHT_PANIC
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let mut taken = TAKEN_PANIC.lock().unwrap_or_else(PoisonError::into_inner);
+    *taken = caught;
+    taken.as_ref().map_or(ptr::null(), |message| message.as_ptr())
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    String::from("unknown panic")
+}
+
+/// Exports the entry points of a frontend plugin, e.g. `capabilities` and the `frontend_`
+/// functions, as `extern "C"` with a panic caught inside the plugin, see [`catch_panic`].
+/// All entry points have to be declared in a single invocation, which also exports
+/// `frontend_take_panic`:
+///
+/// ```ignore
+/// reset_plugin_api::frontend_entries! {
+///     fn frontend_name() -> String {
+///         String::from("Monitors")
+///     }
+///
+///     fn frontend_startup() {}
+/// }
+/// ```
+#[macro_export]
+macro_rules! frontend_entries {
+    ($(
+        $(#[$attribute:meta])*
+        fn $name:ident($($argument:ident: $argument_type:ty),* $(,)?) $(-> $output:ty)? $body:block
+    )*) => {
+        $(
+            $(#[$attribute])*
+            #[no_mangle]
+            #[allow(improper_ctypes_definitions)]
+            pub extern "C" fn $name($($argument: $argument_type),*) $(-> $output)? {
+                $crate::entry::catch_panic(move || $body)
+            }
+        )*
+
+        #[no_mangle]
+        pub extern "C" fn frontend_take_panic() -> *const ::std::ffi::c_char {
+            $crate::entry::take_panic()
+        }
+    };
+}
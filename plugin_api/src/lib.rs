@@ -0,0 +1,60 @@
+use std::any::Any;
+use std::mem::size_of;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use dbus::blocking::SyncConnection;
+
+pub mod entry;
+
+/// Version of [`PluginHost`], increased whenever one of its fields changes.
+pub const PLUGIN_HOST_VERSION: u32 = 1;
+
+pub type BlockingWork = Box<dyn FnOnce() -> Box<dyn Any + Send> + Send>;
+pub type MainLoopUpdate = Box<dyn FnOnce(Box<dyn Any + Send>)>;
+pub type PluginListener = Box<dyn FnOnce(Arc<AtomicBool>) + Send>;
+
+/// Services ReSet offers to plugins, passed to `frontend_host`.
+#[repr(C)]
+pub struct PluginHost {
+    /// The [`PLUGIN_HOST_VERSION`] ReSet was built with, always the first field.
+    pub version: u32,
+    /// Size of the struct in ReSet, always the second field.
+    pub size: usize,
+    /// The session bus connection ReSet uses for method calls.
+    pub connection: fn() -> Result<Arc<SyncConnection>, dbus::Error>,
+    /// Shows a message in the banner of the window.
+    pub show_banner: fn(&str),
+    /// Logs an error and shows it in the banner of the window.
+    pub show_error: fn(&str),
+    /// Runs the work on a separate thread and passes its result to the update on the main loop,
+    /// the update may therefore use widgets.
+    /// Fails without running anything if it is not called from the main thread.
+    pub spawn_blocking: fn(BlockingWork, MainLoopUpdate) -> Result<(), String>,
+    /// Runs a function on the main loop, e.g. to update widgets from a listener.
+    pub invoke_on_main: fn(Box<dyn FnOnce() + Send>),
+    /// Runs a listener on its own thread until the user leaves the current plugin page,
+    /// the listener has to return once the passed flag is false.
+    pub register_listener: fn(PluginListener),
+}
+
+impl PluginHost {
+    /// Checks that the host passed by ReSet has the layout this plugin was built with,
+    /// none of the services may be used otherwise.
+    pub fn check(&self) -> Result<(), String> {
+        if self.version != PLUGIN_HOST_VERSION {
+            return Err(format!(
+                "ReSet provides plugin host {}, the plugin uses {}",
+                self.version, PLUGIN_HOST_VERSION
+            ));
+        }
+        if self.size != size_of::<PluginHost>() {
+            return Err(format!(
+                "ReSet provides a plugin host of {} bytes, the plugin expects {}",
+                self.size,
+                size_of::<PluginHost>()
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+use std::fmt;
+use std::time::Duration;
+
+use dbus::arg::{AppendAll, ReadAll};
+use dbus::blocking::Connection;
+use re_set_lib::audio::audio_structures::{Sink, Source, TAudioObject};
+use re_set_lib::bluetooth::bluetooth_structures::BluetoothDevice;
+use re_set_lib::network::network_structures::AccessPoint;
+
+use crate::cli::status::{status_command, watch_command};
+use crate::components::audio::audio_entry::DBusFunction;
+use crate::components::audio::input::source_const;
+use crate::components::audio::output::sink_const;
+use crate::components::daemon::proxy::audio::{
+    MuteArgs, VolumeArgs, LIST_CARDS, SET_CARD_PROFILE_OF_DEVICE,
+};
+use crate::components::daemon::proxy::{bluetooth, network, DBusMethod};
+
+/// Volume that the daemon treats as 100%.
+pub const NORMAL_VOLUME: u32 = 65536;
+/// Upper bound of the volume sliders, roughly 153%.
+pub const MAX_VOLUME: u32 = 100270;
+
+const COMMANDS: [&str; 6] = ["audio", "wifi", "bluetooth", "card", "status", "watch"];
+
+pub const USAGE: &str = "Usage: ReSet <command> [arguments]
+
+audio get [sink|source]
+audio list [sink|source]
+audio set [sink|source] <volume>     e.g. 50, 50%, +5 or -5
+audio mute [sink|source] [on|off|toggle]
+audio default [sink|source] <name>
+
+wifi list
+wifi connect <ssid> [password]
+wifi disconnect
+
+bluetooth list
+bluetooth connect <address|alias>
+bluetooth disconnect <address|alias>
+bluetooth remove <address|alias>
+
+card list
+card profile <card index|name> <profile>
+
+status --json
+watch --json
+
+--test-plugins [name] [--json|--junit]";
+
+#[derive(Debug)]
+pub enum CommandError {
+    Usage(String),
+    NotFound(String),
+    Failed(String),
+    DBus(dbus::Error),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Usage(message) => write!(formatter, "{}\n\n{}", message, USAGE),
+            CommandError::NotFound(message) => write!(formatter, "Not found: {}", message),
+            CommandError::Failed(message) => write!(formatter, "{}", message),
+            CommandError::DBus(error) => write!(
+                formatter,
+                "Daemon call failed: {}",
+                error.message().unwrap_or("unknown error")
+            ),
+        }
+    }
+}
+
+impl From<dbus::Error> for CommandError {
+    fn from(error: dbus::Error) -> Self {
+        CommandError::DBus(error)
+    }
+}
+
+pub fn is_daemon_command(arguments: &[String]) -> bool {
+    arguments
+        .get(1)
+        .map_or(false, |command| COMMANDS.contains(&command.as_str()))
+}
+
+/// Runs a single headless command against the daemon, e.g. `ReSet audio set sink 50%`.
+pub fn run_daemon_command(arguments: &[String]) -> Result<(), CommandError> {
+    let conn = Connection::new_session()?;
+    let arguments: Vec<&str> = arguments.iter().skip(1).map(String::as_str).collect();
+    match arguments.as_slice() {
+        ["audio", rest @ ..] => audio_command(&conn, rest),
+        ["wifi", rest @ ..] => wifi_command(&conn, rest),
+        ["bluetooth", rest @ ..] => bluetooth_command(&conn, rest),
+        ["card", rest @ ..] => card_command(&conn, rest),
+        ["status", rest @ ..] => status_command(&conn, rest),
+        ["watch", rest @ ..] => watch_command(&conn, rest),
+        _ => Err(CommandError::Usage(String::from("Unknown command"))),
+    }
+}
+
+pub fn call<I: AppendAll, O: ReadAll>(
+    conn: &Connection,
+    method: &DBusMethod<I, O>,
+    args: I,
+    timeout: u64,
+) -> Result<O, CommandError> {
+    Ok(method.call_on(conn, args, Duration::from_millis(timeout))?)
+}
+
+fn expect_success(result: (bool,), message: &str) -> Result<(), CommandError> {
+    if result.0 {
+        Ok(())
+    } else {
+        Err(CommandError::Failed(String::from(message)))
+    }
+}
+
+struct AudioFunctions<AudioObject: 'static> {
+    list: &'static DBusFunction<(), (Vec<AudioObject>,)>,
+    get_default: &'static DBusFunction<(), (AudioObject,)>,
+    set_default: &'static DBusFunction<(String,), (AudioObject,)>,
+    set_volume: &'static DBusFunction<VolumeArgs, ()>,
+    set_mute: &'static DBusFunction<MuteArgs, ()>,
+}
+
+const SINK_FUNCTIONS: AudioFunctions<Sink> = AudioFunctions {
+    list: &sink_const::GETOBJECTS,
+    get_default: &sink_const::GETDEFAULT,
+    set_default: &sink_const::SETDEFAULT,
+    set_volume: &sink_const::SETVOLUME,
+    set_mute: &sink_const::SETMUTE,
+};
+
+const SOURCE_FUNCTIONS: AudioFunctions<Source> = AudioFunctions {
+    list: &source_const::GETOBJECTS,
+    get_default: &source_const::GETDEFAULT,
+    set_default: &source_const::SETDEFAULT,
+    set_volume: &source_const::SETVOLUME,
+    set_mute: &source_const::SETMUTE,
+};
+
+fn audio_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    let (action, arguments) = match arguments.split_first() {
+        Some((action, arguments)) => (*action, arguments),
+        None => return Err(CommandError::Usage(String::from("Missing audio action"))),
+    };
+    match arguments.first() {
+        Some(&"source") => audio_action::<Source>(conn, action, &arguments[1..], &SOURCE_FUNCTIONS),
+        Some(&"sink") => audio_action::<Sink>(conn, action, &arguments[1..], &SINK_FUNCTIONS),
+        _ => audio_action::<Sink>(conn, action, arguments, &SINK_FUNCTIONS),
+    }
+}
+
+fn audio_action<AudioObject: TAudioObject>(
+    conn: &Connection,
+    action: &str,
+    arguments: &[&str],
+    functions: &AudioFunctions<AudioObject>,
+) -> Result<(), CommandError> {
+    match (action, arguments) {
+        ("get", []) => {
+            let (object,) = call(conn, &functions.get_default.function, (), 1000)?;
+            print_audio_object(&object, true);
+            Ok(())
+        }
+        ("list", []) => {
+            let (default,) = call(conn, &functions.get_default.function, (), 1000)?;
+            let (objects,) = call(conn, &functions.list.function, (), 1000)?;
+            for object in objects {
+                let is_default = object.index() == default.index();
+                print_audio_object(&object, is_default);
+            }
+            Ok(())
+        }
+        ("set", [volume]) => {
+            let (object,) = call(conn, &functions.get_default.function, (), 1000)?;
+            let current = *object.volume().first().unwrap_or(&0);
+            let volume = parse_volume(volume, current)?;
+            call(
+                conn,
+                &functions.set_volume.function,
+                (object.index(), object.channels(), volume),
+                1000,
+            )
+        }
+        ("mute", [] | [_]) => {
+            let (object,) = call(conn, &functions.get_default.function, (), 1000)?;
+            let muted = match arguments.first() {
+                None | Some(&"toggle") => !object.muted(),
+                Some(&"on") => true,
+                Some(&"off") => false,
+                Some(state) => {
+                    return Err(CommandError::Usage(format!(
+                        "Unknown mute state: {}",
+                        state
+                    )))
+                }
+            };
+            call(
+                conn,
+                &functions.set_mute.function,
+                (object.index(), muted),
+                1000,
+            )
+        }
+        ("default", [name]) => {
+            let (objects,) = call(conn, &functions.list.function, (), 1000)?;
+            let object = objects
+                .iter()
+                .find(|object| object.name() == *name || object.alias() == *name)
+                .ok_or_else(|| CommandError::NotFound(name.to_string()))?;
+            let (object,) = call(
+                conn,
+                &functions.set_default.function,
+                (object.name(),),
+                1000,
+            )?;
+            print_audio_object(&object, true);
+            Ok(())
+        }
+        _ => Err(CommandError::Usage(format!(
+            "Invalid arguments for audio {}",
+            action
+        ))),
+    }
+}
+
+fn print_audio_object<AudioObject: TAudioObject>(object: &AudioObject, is_default: bool) {
+    let volume = *object.volume().first().unwrap_or(&0);
+    println!(
+        "{} {}\t{}\t{}%{}",
+        if is_default { "*" } else { " " },
+        object.index(),
+        object.alias(),
+        volume_percentage(volume),
+        if object.muted() { "\tmuted" } else { "" }
+    );
+}
+
+pub fn volume_percentage(volume: u32) -> f64 {
+    (volume as f64 / 655.36).round()
+}
+
+/// Parses absolute (`50`, `50%`) or relative (`+5`, `-5%`) volumes in percent.
+pub fn parse_volume(value: &str, current: u32) -> Result<u32, CommandError> {
+    let invalid = || CommandError::Usage(format!("Invalid volume: {}", value));
+    let trimmed = value.trim_end_matches('%');
+    let relative = trimmed.starts_with('+') || trimmed.starts_with('-');
+    let percentage: f64 = trimmed.parse().map_err(|_| invalid())?;
+    if !percentage.is_finite() {
+        return Err(invalid());
+    }
+    let mut volume = percentage * NORMAL_VOLUME as f64 / 100.0;
+    if relative {
+        volume += current as f64;
+    }
+    Ok(volume.round().clamp(0.0, MAX_VOLUME as f64) as u32)
+}
+
+fn ssid_name(access_point: &AccessPoint) -> String {
+    String::from_utf8_lossy(&access_point.ssid).to_string()
+}
+
+fn wifi_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    match arguments {
+        ["list"] => {
+            let (device,) = call(conn, &network::GET_CURRENT_WIFI_DEVICE, (), 1000)?;
+            let (access_points,) = call(conn, &network::LIST_ACCESS_POINTS, (), 1000)?;
+            for access_point in access_points {
+                if access_point.ssid.is_empty() {
+                    continue;
+                }
+                println!(
+                    "{} {}\t{}%{}",
+                    if access_point.ssid == device.active_access_point {
+                        "*"
+                    } else {
+                        " "
+                    },
+                    ssid_name(&access_point),
+                    access_point.strength,
+                    if access_point.stored { "\tstored" } else { "" }
+                );
+            }
+            Ok(())
+        }
+        ["connect", ssid, password @ ..] if password.len() <= 1 => {
+            let (access_points,) = call(conn, &network::LIST_ACCESS_POINTS, (), 1000)?;
+            let access_point = access_points
+                .into_iter()
+                .find(|access_point| ssid_name(access_point) == *ssid)
+                .ok_or_else(|| CommandError::NotFound(ssid.to_string()))?;
+            let result = match password.first() {
+                Some(password) => call(
+                    conn,
+                    &network::CONNECT_TO_NEW_ACCESS_POINT,
+                    (access_point, password.to_string()),
+                    10000,
+                )?,
+                None if access_point.stored => call(
+                    conn,
+                    &network::CONNECT_TO_KNOWN_ACCESS_POINT,
+                    (access_point,),
+                    10000,
+                )?,
+                None => {
+                    return Err(CommandError::Usage(format!(
+                        "{} is not stored, a password is required",
+                        ssid
+                    )))
+                }
+            };
+            expect_success(result, "Could not connect to access point")
+        }
+        ["disconnect"] => {
+            let result = call(
+                conn,
+                &network::DISCONNECT_FROM_CURRENT_ACCESS_POINT,
+                (),
+                10000,
+            )?;
+            expect_success(result, "Could not disconnect from access point")
+        }
+        _ => Err(CommandError::Usage(String::from(
+            "Invalid arguments for wifi",
+        ))),
+    }
+}
+
+fn find_bluetooth_device(conn: &Connection, device: &str) -> Result<BluetoothDevice, CommandError> {
+    let (devices,) = call(conn, &bluetooth::GET_BLUETOOTH_DEVICES, (), 1000)?;
+    devices
+        .into_iter()
+        .find(|bluetooth_device| {
+            bluetooth_device.address.eq_ignore_ascii_case(device)
+                || bluetooth_device.alias == device
+        })
+        .ok_or_else(|| CommandError::NotFound(device.to_string()))
+}
+
+fn bluetooth_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    match arguments {
+        ["list"] => {
+            let (devices,) = call(conn, &bluetooth::GET_BLUETOOTH_DEVICES, (), 1000)?;
+            for device in devices {
+                println!(
+                    "{} {}\t{}{}",
+                    if device.connected { "*" } else { " " },
+                    device.address,
+                    device.alias,
+                    if device.paired { "\tpaired" } else { "" }
+                );
+            }
+            Ok(())
+        }
+        ["connect", device] => {
+            let device = find_bluetooth_device(conn, device)?;
+            let result = call(
+                conn,
+                &bluetooth::CONNECT_TO_BLUETOOTH_DEVICE,
+                (device.path,),
+                10000,
+            )?;
+            expect_success(result, "Could not connect to device")
+        }
+        ["disconnect", device] => {
+            let device = find_bluetooth_device(conn, device)?;
+            let result = call(
+                conn,
+                &bluetooth::DISCONNECT_FROM_BLUETOOTH_DEVICE,
+                (device.path,),
+                10000,
+            )?;
+            expect_success(result, "Could not disconnect from device")
+        }
+        ["remove", device] => {
+            let device = find_bluetooth_device(conn, device)?;
+            let result = call(
+                conn,
+                &bluetooth::REMOVE_DEVICE_PAIRING,
+                (device.path,),
+                1000,
+            )?;
+            expect_success(result, "Failed to remove device pairing")
+        }
+        _ => Err(CommandError::Usage(String::from(
+            "Invalid arguments for bluetooth",
+        ))),
+    }
+}
+
+fn card_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    let (cards,) = call(conn, &LIST_CARDS, (), 1000)?;
+    match arguments {
+        ["list"] => {
+            for card in cards {
+                println!("{}\t{}", card.index, card.name);
+                for profile in card.profiles {
+                    println!(
+                        "  {} {}\t{}",
+                        if profile.name == card.active_profile {
+                            "*"
+                        } else {
+                            " "
+                        },
+                        profile.name,
+                        profile.description
+                    );
+                }
+            }
+            Ok(())
+        }
+        ["profile", card, profile] => {
+            let card = cards
+                .iter()
+                .find(|entry| entry.name == *card || entry.index.to_string() == *card)
+                .ok_or_else(|| CommandError::NotFound(card.to_string()))?;
+            let profile = card
+                .profiles
+                .iter()
+                .find(|entry| entry.name == *profile || entry.description == *profile)
+                .ok_or_else(|| CommandError::NotFound(profile.to_string()))?;
+            call(
+                conn,
+                &SET_CARD_PROFILE_OF_DEVICE,
+                (card.index, profile.name.clone()),
+                1000,
+            )
+        }
+        _ => Err(CommandError::Usage(String::from(
+            "Invalid arguments for card",
+        ))),
+    }
+}
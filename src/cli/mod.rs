@@ -0,0 +1,4 @@
+pub mod commands;
+pub mod page;
+pub mod plugin_tests;
+pub mod status;
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::components::base::utils::Position;
+
+pub const PAGE_FLAG: &str = "--page";
+pub const QUICK_FLAG: &str = "--quick";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PageArgumentError {
+    MissingValue,
+    UnknownPage(String),
+}
+
+impl fmt::Display for PageArgumentError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PageArgumentError::MissingValue => {
+                write!(formatter, "{} requires a page name", PAGE_FLAG)
+            }
+            PageArgumentError::UnknownPage(page) => write!(
+                formatter,
+                "Unknown page: {}, expected one of home, connectivity, wifi, bluetooth, audio, output, input, plugins or custom:<name>",
+                page
+            ),
+        }
+    }
+}
+
+/// Searches the command line for `--page <name>` or `--page=<name>`.
+/// The first argument is expected to be the binary name and is skipped.
+pub fn parse_page_argument(arguments: &[String]) -> Result<Option<Position>, PageArgumentError> {
+    let mut iter = arguments.iter().skip(1).map(String::as_str);
+    while let Some(argument) = iter.next() {
+        let page = if argument == PAGE_FLAG {
+            iter.next().ok_or(PageArgumentError::MissingValue)?
+        } else if let Some(page) = argument.strip_prefix("--page=") {
+            page
+        } else {
+            continue;
+        };
+        return match Position::from_page_name(page) {
+            Some(position) => Ok(Some(position)),
+            None => Err(PageArgumentError::UnknownPage(page.to_string())),
+        };
+    }
+    Ok(None)
+}
+
+/// Whether `--quick` asks for the compact quick settings window instead of the main window.
+/// The first argument is expected to be the binary name and is skipped.
+pub fn is_quick_argument(arguments: &[String]) -> bool {
+    arguments
+        .iter()
+        .skip(1)
+        .any(|argument| argument == QUICK_FLAG)
+}
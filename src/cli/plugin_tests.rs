@@ -0,0 +1,330 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use re_set_lib::utils::plugin::PluginTestFunc;
+use re_set_lib::utils::plugin_setup::FRONTEND_PLUGINS;
+use reset_plugin_api::entry::panic_message;
+use serde_json::{json, Value};
+
+use crate::cli::commands::CommandError;
+use crate::cli::status::{print_json, JSON_FLAG};
+use crate::components::plugin::abi::check_plugin_abi;
+use crate::components::plugin::manager::{
+    call_plugin, library_file_name, panic_taker, plugin_library,
+};
+
+pub const TEST_PLUGINS_FLAG: &str = "--test-plugins";
+pub const JUNIT_FLAG: &str = "--junit";
+/// Not meant for users, the hot reload checks new builds of plugins with it.
+pub const CHECK_PLUGIN_ABI_FLAG: &str = "--check-plugin-abi";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    JUnit,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PluginTestOptions {
+    /// Name or library file name of the only plugin to test.
+    pub plugin: Option<String>,
+    pub format: ReportFormat,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    Failed(String),
+    Panicked(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub name: String,
+    pub duration: Duration,
+    pub outcome: TestOutcome,
+}
+
+/// The results of a single plugin, `error` is set if its tests could not be run at all.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginTestReport {
+    pub plugin: String,
+    pub file: String,
+    pub error: Option<String>,
+    pub tests: Vec<TestResult>,
+}
+
+impl PluginTestReport {
+    pub fn successful(&self) -> bool {
+        self.error.is_none()
+            && self
+                .tests
+                .iter()
+                .all(|test| test.outcome == TestOutcome::Passed)
+    }
+
+    fn duration(&self) -> Duration {
+        self.tests.iter().map(|test| test.duration).sum()
+    }
+
+    fn count(&self, matches: fn(&TestOutcome) -> bool) -> usize {
+        self.tests
+            .iter()
+            .filter(|test| matches(&test.outcome))
+            .count()
+    }
+}
+
+pub fn is_plugin_test_command(arguments: &[String]) -> bool {
+    arguments
+        .get(1)
+        .map_or(false, |flag| flag == TEST_PLUGINS_FLAG)
+}
+
+pub fn is_plugin_abi_check_command(arguments: &[String]) -> bool {
+    arguments
+        .get(1)
+        .map_or(false, |flag| flag == CHECK_PLUGIN_ABI_FLAG)
+}
+
+/// Loads the library passed after `--check-plugin-abi` and checks its ABI declaration,
+/// so that the constructors of an incompatible build only run in this process.
+pub fn run_plugin_abi_check(arguments: &[String]) -> Result<(), CommandError> {
+    let path = arguments
+        .get(2)
+        .ok_or_else(|| CommandError::Usage(String::from("Missing plugin library")))?;
+    let _library = unsafe { libloading::Library::new(path) }
+        .map_err(|error| CommandError::Failed(format!("could not load the build: {}", error)))?;
+    check_plugin_abi(Path::new(path)).map_err(CommandError::Failed)
+}
+
+/// Parses `--test-plugins [name] [--json|--junit]`, the binary name is skipped.
+pub fn parse_plugin_test_arguments(
+    arguments: &[String],
+) -> Result<PluginTestOptions, CommandError> {
+    let mut options = PluginTestOptions {
+        plugin: None,
+        format: ReportFormat::Json,
+    };
+    for argument in arguments.iter().skip(2) {
+        match argument.as_str() {
+            JSON_FLAG => options.format = ReportFormat::Json,
+            JUNIT_FLAG => options.format = ReportFormat::JUnit,
+            flag if flag.starts_with("--") => {
+                return Err(CommandError::Usage(format!("Unknown flag {}", flag)))
+            }
+            name if options.plugin.is_none() => options.plugin = Some(name.to_string()),
+            _ => {
+                return Err(CommandError::Usage(String::from(
+                    "Only a single plugin can be tested at once",
+                )))
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Runs the tests of the frontend plugins and prints a report to stdout.
+/// Returns whether every test passed.
+pub fn run_plugin_tests(arguments: &[String]) -> Result<bool, CommandError> {
+    let options = parse_plugin_test_arguments(arguments)?;
+    let reports = test_plugins(options.plugin.as_deref());
+    if let Some(plugin) = &options.plugin {
+        if reports.is_empty() {
+            return Err(CommandError::NotFound(format!("plugin {}", plugin)));
+        }
+    }
+    match options.format {
+        ReportFormat::Json => print_json(&json_report(&reports)),
+        ReportFormat::JUnit => {
+            let mut stdout = io::stdout().lock();
+            let _ = write!(stdout, "{}", junit_report(&reports));
+            let _ = stdout.flush();
+        }
+    }
+    Ok(reports.iter().all(PluginTestReport::successful))
+}
+
+/// Tests every loaded frontend plugin, or only the one with the given name or file name.
+pub fn test_plugins(plugin_name: Option<&str>) -> Vec<PluginTestReport> {
+    let mut reports = Vec::new();
+    unsafe {
+        for plugin in FRONTEND_PLUGINS.iter() {
+            let library = plugin_library(plugin);
+            let file = library
+                .as_deref()
+                .map(library_file_name)
+                .unwrap_or_default();
+            let mut report = PluginTestReport {
+                plugin: file.clone(),
+                file,
+                error: None,
+                tests: Vec::new(),
+            };
+            // functions returning rust types may only be called with a matching ABI
+            let abi = library
+                .as_deref()
+                .ok_or_else(|| String::from("could not find the plugin library"))
+                .and_then(check_plugin_abi);
+            let take_panic = library.as_deref().and_then(panic_taker);
+            let name = abi.and_then(|_| {
+                call_plugin("frontend_name", take_panic, || (plugin.frontend_name)())
+                    .map_err(|error| error.to_string())
+            });
+            match name {
+                Ok(name) => report.plugin = name,
+                Err(error) => report.error = Some(error),
+            }
+            let selected = plugin_name.map_or(true, |plugin_name| {
+                report.plugin.eq_ignore_ascii_case(plugin_name)
+                    || report.file.eq_ignore_ascii_case(plugin_name)
+            });
+            if !selected {
+                continue;
+            }
+            if report.error.is_none() {
+                match call_plugin("frontend_tests", take_panic, || (plugin.frontend_tests)()) {
+                    Ok(tests) => report.tests = tests.into_iter().map(run_test).collect(),
+                    Err(error) => report.error = Some(error.to_string()),
+                }
+            }
+            reports.push(report);
+        }
+    }
+    reports
+}
+
+/// Runs a test on its own thread like `plugin_tests`, so that panics are captured.
+fn run_test(test: PluginTestFunc) -> TestResult {
+    let name = test.name().to_string();
+    let start = Instant::now();
+    let outcome = match thread::spawn(test).join() {
+        Ok(Ok(())) => TestOutcome::Passed,
+        Ok(Err(error)) => TestOutcome::Failed(error.message().clone()),
+        Err(payload) => TestOutcome::Panicked(panic_message(payload)),
+    };
+    TestResult {
+        name,
+        duration: start.elapsed(),
+        outcome,
+    }
+}
+
+pub fn json_report(reports: &[PluginTestReport]) -> Value {
+    let count = |matches: fn(&TestOutcome) -> bool| -> usize {
+        reports.iter().map(|report| report.count(matches)).sum()
+    };
+    json!({
+        "passed": count(|outcome| matches!(outcome, TestOutcome::Passed)),
+        "failed": count(|outcome| matches!(outcome, TestOutcome::Failed(_))),
+        "panicked": count(|outcome| matches!(outcome, TestOutcome::Panicked(_))),
+        "errors": reports.iter().filter(|report| report.error.is_some()).count(),
+        "plugins": reports.iter().map(|report| json!({
+            "name": report.plugin,
+            "file": report.file,
+            "error": report.error,
+            "tests": report.tests.iter().map(|test| {
+                let (outcome, message) = match &test.outcome {
+                    TestOutcome::Passed => ("passed", None),
+                    TestOutcome::Failed(message) => ("failed", Some(message)),
+                    TestOutcome::Panicked(message) => ("panicked", Some(message)),
+                };
+                json!({
+                    "name": test.name,
+                    "outcome": outcome,
+                    "message": message,
+                    "duration_ms": test.duration.as_secs_f64() * 1000.0,
+                })
+            }).collect::<Vec<Value>>(),
+        })).collect::<Vec<Value>>(),
+    })
+}
+
+/// Formats the reports as JUnit XML, each plugin is a test suite.
+/// Plugins whose tests could not be run report a single erroneous `frontend_tests` case.
+pub fn junit_report(reports: &[PluginTestReport]) -> String {
+    let tests: usize = reports
+        .iter()
+        .map(|report| report.tests.len().max(report.error.is_some() as usize))
+        .sum();
+    let failures: usize = reports
+        .iter()
+        .map(|report| report.count(|outcome| matches!(outcome, TestOutcome::Failed(_))))
+        .sum();
+    let errors: usize = reports
+        .iter()
+        .map(|report| {
+            report.count(|outcome| matches!(outcome, TestOutcome::Panicked(_)))
+                + report.error.is_some() as usize
+        })
+        .sum();
+    let time: Duration = reports.iter().map(PluginTestReport::duration).sum();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += &format!(
+        "<testsuites name=\"ReSet plugins\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+        tests,
+        failures,
+        errors,
+        time.as_secs_f64()
+    );
+    for report in reports {
+        let plugin = escape_xml(&report.plugin);
+        xml += &format!(
+            "  <testsuite name=\"{}\" file=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+            plugin,
+            escape_xml(&report.file),
+            report.tests.len().max(report.error.is_some() as usize),
+            report.count(|outcome| matches!(outcome, TestOutcome::Failed(_))),
+            report.count(|outcome| matches!(outcome, TestOutcome::Panicked(_)))
+                + report.error.is_some() as usize,
+            report.duration().as_secs_f64()
+        );
+        if let Some(error) = &report.error {
+            xml += &format!(
+                "    <testcase name=\"frontend_tests\" classname=\"{}\" time=\"0.000\">\n      <error type=\"load\" message=\"{}\"/>\n    </testcase>\n",
+                plugin,
+                escape_xml(error)
+            );
+        }
+        for test in &report.tests {
+            let case = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                escape_xml(&test.name),
+                plugin,
+                test.duration.as_secs_f64()
+            );
+            xml += &match &test.outcome {
+                TestOutcome::Passed => format!("{}/>\n", case),
+                TestOutcome::Failed(message) => format!(
+                    "{}>\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                    case,
+                    escape_xml(message)
+                ),
+                TestOutcome::Panicked(message) => format!(
+                    "{}>\n      <error type=\"panic\" message=\"{}\"/>\n    </testcase>\n",
+                    case,
+                    escape_xml(message)
+                ),
+            };
+        }
+        xml += "  </testsuite>\n";
+    }
+    xml += "</testsuites>\n";
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '&' => String::from("&amp;"),
+            '<' => String::from("&lt;"),
+            '>' => String::from("&gt;"),
+            '"' => String::from("&quot;"),
+            '\'' => String::from("&apos;"),
+            '\n' => String::from("&#10;"),
+            character => character.to_string(),
+        })
+        .collect()
+}
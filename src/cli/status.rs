@@ -0,0 +1,297 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use dbus::arg::ReadAll;
+use dbus::blocking::Connection;
+use dbus::message::SignalArgs;
+use dbus::Path;
+use re_set_lib::audio::audio_structures::{Sink, Source, TAudioObject, TAudioStreamObject};
+use re_set_lib::bluetooth::bluetooth_structures::BluetoothDevice;
+use re_set_lib::network::network_structures::{AccessPoint, WifiDevice};
+use re_set_lib::signals::{
+    AccessPointAdded, AccessPointChanged, AccessPointRemoved, BluetoothDeviceAdded,
+    BluetoothDeviceChanged, BluetoothDeviceRemoved, InputStreamAdded, InputStreamChanged,
+    InputStreamRemoved, OutputStreamAdded, OutputStreamChanged, OutputStreamRemoved, SinkAdded,
+    SinkChanged, SinkRemoved, SourceAdded, SourceChanged, SourceRemoved, TAudioEventRemoved,
+    TAudioObjectEvent, TAudioStreamEvent, WifiDeviceChanged,
+};
+use serde_json::{json, Value};
+
+use crate::cli::commands::{call, volume_percentage, CommandError};
+use crate::components::audio::input::source_const;
+use crate::components::audio::output::sink_const;
+use crate::components::daemon::proxy::{bluetooth, network};
+use crate::components::utils::{BASE, DBUS_PATH};
+
+pub const JSON_FLAG: &str = "--json";
+
+/// Set by SIGINT or SIGTERM while `watch` is running.
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+fn expect_json_flag(command: &str, arguments: &[&str]) -> Result<(), CommandError> {
+    match arguments {
+        [JSON_FLAG] => Ok(()),
+        _ => Err(CommandError::Usage(format!(
+            "{} currently only supports {} output",
+            command, JSON_FLAG
+        ))),
+    }
+}
+
+/// Prints a single JSON object describing the current audio, Wi-Fi and Bluetooth state.
+/// Sections that the daemon cannot provide, e.g. missing Bluetooth, are reported as null.
+pub fn status_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    expect_json_flag("status", arguments)?;
+    let sink = call(conn, &sink_const::GETDEFAULT.function, (), 1000).ok();
+    let source = call(conn, &source_const::GETDEFAULT.function, (), 1000).ok();
+    let status = json!({
+        "audio": {
+            "sink": sink.map(|(sink,)| audio_object_json(&sink)),
+            "source": source.map(|(source,)| audio_object_json(&source)),
+        },
+        "wifi": wifi_status(conn),
+        "bluetooth": bluetooth_status(conn),
+    });
+    print_json(&status);
+    Ok(())
+}
+
+fn wifi_status(conn: &Connection) -> Value {
+    let device = match call(conn, &network::GET_CURRENT_WIFI_DEVICE, (), 1000) {
+        Ok((device,)) => device,
+        Err(_) => return Value::Null,
+    };
+    let access_points = call(conn, &network::LIST_ACCESS_POINTS, (), 1000)
+        .map(|(access_points,)| access_points)
+        .unwrap_or_default();
+    wifi_status_json(&device, &access_points)
+}
+
+/// The Wi-Fi section of `status`, the access point is null while not connected.
+pub fn wifi_status_json(device: &WifiDevice, access_points: &[AccessPoint]) -> Value {
+    let active_access_point = access_points
+        .iter()
+        .find(|access_point| {
+            !access_point.ssid.is_empty() && access_point.ssid == device.active_access_point
+        })
+        .map(access_point_json);
+    json!({
+        "device": wifi_device_json(device),
+        "access_point": active_access_point,
+    })
+}
+
+fn bluetooth_status(conn: &Connection) -> Value {
+    match call(conn, &bluetooth::GET_BLUETOOTH_DEVICES, (), 1000) {
+        Ok((devices,)) => json!({
+            "devices": devices.iter().map(bluetooth_device_json).collect::<Vec<Value>>(),
+        }),
+        Err(_) => Value::Null,
+    }
+}
+
+/// Prints one JSON line per daemon signal until the process is interrupted or terminated.
+pub fn watch_command(conn: &Connection, arguments: &[&str]) -> Result<(), CommandError> {
+    expect_json_flag("watch", arguments)?;
+
+    watch_signal(conn, |signal: &AccessPointAdded| {
+        access_point_json(&signal.access_point)
+    })?;
+    watch_signal(conn, |signal: &AccessPointChanged| {
+        access_point_json(&signal.access_point)
+    })?;
+    watch_signal(conn, |signal: &AccessPointRemoved| {
+        path_json(&signal.access_point)
+    })?;
+    watch_signal(conn, |signal: &WifiDeviceChanged| {
+        wifi_device_json(&signal.wifi_device)
+    })?;
+    watch_signal(conn, |signal: &BluetoothDeviceAdded| {
+        bluetooth_device_json(&signal.bluetooth_device)
+    })?;
+    watch_signal(conn, |signal: &BluetoothDeviceChanged| {
+        bluetooth_device_json(&signal.bluetooth_device)
+    })?;
+    watch_signal(conn, |signal: &BluetoothDeviceRemoved| {
+        path_json(&signal.bluetooth_device)
+    })?;
+    watch_signal(conn, audio_object_event_json::<Sink, SinkAdded>)?;
+    watch_signal(conn, audio_object_event_json::<Sink, SinkChanged>)?;
+    watch_signal(conn, removed_event_json::<SinkRemoved>)?;
+    watch_signal(conn, audio_object_event_json::<Source, SourceAdded>)?;
+    watch_signal(conn, audio_object_event_json::<Source, SourceChanged>)?;
+    watch_signal(conn, removed_event_json::<SourceRemoved>)?;
+    watch_signal(conn, |signal: &InputStreamAdded| {
+        audio_stream_json(signal.stream_ref())
+    })?;
+    watch_signal(conn, |signal: &InputStreamChanged| {
+        audio_stream_json(signal.stream_ref())
+    })?;
+    watch_signal(conn, removed_event_json::<InputStreamRemoved>)?;
+    watch_signal(conn, |signal: &OutputStreamAdded| {
+        audio_stream_json(signal.stream_ref())
+    })?;
+    watch_signal(conn, |signal: &OutputStreamChanged| {
+        audio_stream_json(signal.stream_ref())
+    })?;
+    watch_signal(conn, removed_event_json::<OutputStreamRemoved>)?;
+
+    install_stop_handler();
+    let network_listener_started = start_listeners(conn);
+    // a signal interrupts processing, the flag is checked right after
+    let mut result = Ok(());
+    while !STOP_REQUESTED.load(Ordering::SeqCst) {
+        if let Err(error) = conn.process(Duration::from_millis(1000)) {
+            if !STOP_REQUESTED.load(Ordering::SeqCst) {
+                result = Err(error.into());
+                break;
+            }
+        }
+    }
+    stop_listeners(conn, network_listener_started);
+    result
+}
+
+extern "C" fn request_stop(_: libc::c_int) {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Lets SIGINT and SIGTERM end the watch, so that the listeners can be stopped before exiting.
+fn install_stop_handler() {
+    let handler = request_stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+/// The daemon only emits network and bluetooth signals while a listener is active.
+/// A listener that cannot be started is reported, the other events are still printed.
+/// Returns whether this watch started the network listener of the daemon.
+fn start_listeners(conn: &Connection) -> bool {
+    let network_listener_started = match call(conn, &network::START_NETWORK_LISTENER, (), 1000) {
+        Ok((started,)) => started,
+        Err(error) => {
+            eprintln!("Warning: network events unavailable: {}", error);
+            false
+        }
+    };
+    if let Err(error) = call(conn, &bluetooth::START_BLUETOOTH_LISTENER, (), 1000) {
+        eprintln!("Warning: bluetooth events unavailable: {}", error);
+    }
+    network_listener_started
+}
+
+/// The listeners of the daemon are shared with every other client, e.g. an open ReSet window.
+/// Only a network listener started by this watch is stopped, the daemon does not report
+/// whether the bluetooth listener was already running, so it is left running.
+fn stop_listeners(conn: &Connection, network_listener_started: bool) {
+    if !network_listener_started {
+        return;
+    }
+    if let Err(error) = call(conn, &network::STOP_NETWORK_LISTENER, (), 1000) {
+        eprintln!("Warning: could not stop the network listener: {}", error);
+    }
+}
+
+fn watch_signal<Signal: ReadAll + SignalArgs + 'static>(
+    conn: &Connection,
+    to_json: fn(&Signal) -> Value,
+) -> Result<(), CommandError> {
+    let rule = Signal::match_rule(Some(&BASE.into()), Some(&Path::from(DBUS_PATH))).static_clone();
+    conn.add_match(rule, move |signal: Signal, _, _| {
+        print_json(&event_json(Signal::NAME, to_json(&signal)));
+        true
+    })?;
+    Ok(())
+}
+
+/// A single line printed by `watch`.
+pub fn event_json(event: &str, data: Value) -> Value {
+    json!({
+        "event": event,
+        "data": data,
+    })
+}
+
+pub fn print_json(value: &Value) {
+    let mut stdout = io::stdout().lock();
+    // a closed pipe simply means nobody is listening anymore
+    let _ = writeln!(stdout, "{}", value);
+    let _ = stdout.flush();
+}
+
+fn audio_object_event_json<AudioObject: TAudioObject, Event: TAudioObjectEvent<AudioObject>>(
+    event: &Event,
+) -> Value {
+    audio_object_json(event.object_ref())
+}
+
+pub fn removed_event_json<Event: TAudioEventRemoved>(event: &Event) -> Value {
+    json!({ "index": event.index() })
+}
+
+pub fn path_json(path: &Path<'static>) -> Value {
+    json!({ "path": path.to_string() })
+}
+
+pub fn audio_object_json<AudioObject: TAudioObject>(object: &AudioObject) -> Value {
+    let volume = *object.volume().first().unwrap_or(&0);
+    json!({
+        "index": object.index(),
+        "name": object.name(),
+        "alias": object.alias(),
+        "channels": object.channels(),
+        "volume": volume,
+        "volume_percent": volume_percentage(volume),
+        "muted": object.muted(),
+    })
+}
+
+pub fn audio_stream_json<StreamObject: TAudioStreamObject>(stream: &StreamObject) -> Value {
+    let volume = *stream.volume().first().unwrap_or(&0);
+    json!({
+        "index": stream.index(),
+        "name": stream.name(),
+        "application_name": stream.application_name(),
+        "object_index": stream.audio_object_index(),
+        "volume": volume,
+        "volume_percent": volume_percentage(volume),
+        "muted": stream.muted(),
+        "corked": stream.corked(),
+    })
+}
+
+pub fn access_point_json(access_point: &AccessPoint) -> Value {
+    json!({
+        "ssid": String::from_utf8_lossy(&access_point.ssid),
+        "strength": access_point.strength,
+        "stored": access_point.stored,
+        "path": access_point.dbus_path.to_string(),
+    })
+}
+
+pub fn wifi_device_json(device: &WifiDevice) -> Value {
+    json!({
+        "name": device.name,
+        "path": device.path.to_string(),
+        "active_access_point": String::from_utf8_lossy(&device.active_access_point),
+    })
+}
+
+pub fn bluetooth_device_json(device: &BluetoothDevice) -> Value {
+    json!({
+        "alias": device.alias,
+        "name": device.name,
+        "address": device.address,
+        "icon": device.icon,
+        "path": device.path.to_string(),
+        "rssi": device.rssi,
+        "paired": device.paired,
+        "trusted": device.trusted,
+        "bonded": device.bonded,
+        "blocked": device.blocked,
+        "connected": device.connected,
+    })
+}
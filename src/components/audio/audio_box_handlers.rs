@@ -19,7 +19,10 @@ use re_set_lib::{
     signals::{TAudioEventRemoved, TAudioObjectEvent, TAudioStreamEvent},
 };
 
-use crate::components::base::{error_impl::ReSetErrorImpl, list_entry::ListEntry};
+use crate::components::{
+    base::{error_impl::ReSetErrorImpl, list_entry::ListEntry},
+    daemon::proxy::audio::{MuteArgs, VolumeArgs},
+};
 
 use super::{
     audio_box_utils::{
@@ -45,7 +48,7 @@ pub fn mute_clicked_handler<
     AudioBoxImpl: TAudioBoxImpl<AudioObject, AudioEntry, AudioStream>,
 >(
     audio_box: Arc<AudioBox>,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<MuteArgs, ()>,
 ) {
     let imp = audio_box.box_imp();
     let source = imp.default_audio_object();
@@ -77,7 +80,7 @@ pub fn volume_slider_handler<
 >(
     audio_box: Arc<AudioBox>,
     value: f64,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<VolumeArgs, ()>,
 ) -> Propagation {
     let imp = audio_box.box_imp();
     let fraction = (value / 655.36).round();
@@ -114,7 +117,7 @@ pub fn dropdown_handler<
 >(
     audio_box: Arc<AudioBox>,
     dropdown: &adw::ComboRow,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<(String,), (AudioObject,)>,
 ) -> ControlFlow {
     let source_box_imp = audio_box.box_imp();
     let source_box_ref = audio_box.clone();
@@ -133,9 +136,9 @@ pub fn dropdown_handler<
     }
     let source = Arc::new(source.unwrap().1.clone());
     gio::spawn_blocking(move || {
-        let result = audio_dbus_call::<AudioBox, (AudioObject,), (&String,)>(
+        let result = audio_dbus_call::<AudioBox, (AudioObject,), (String,)>(
             source_box_ref.clone(),
-            (&source,),
+            (source.to_string(),),
             function,
         );
         if result.is_none() {
@@ -167,12 +170,12 @@ pub fn populate_audio_objects<
     AudioBoxImpl: TAudioBoxImpl<AudioObject, AudioEntry, AudioStream>,
 >(
     audio_box: Arc<AudioBox>,
-    audio_objects_function: &'static DBusFunction,
-    default_audio_object_function: &'static DBusFunction,
-    set_default_audio_object_function: &'static DBusFunction,
-    get_audio_streams_function: &'static DBusFunction,
-    set_audio_object_volume_function: &'static DBusFunction,
-    set_audio_object_mute_function: &'static DBusFunction,
+    audio_objects_function: &'static DBusFunction<(), (Vec<AudioObject>,)>,
+    default_audio_object_function: &'static DBusFunction<(), (AudioObject,)>,
+    set_default_audio_object_function: &'static DBusFunction<(String,), (AudioObject,)>,
+    get_audio_streams_function: &'static DBusFunction<(), (Vec<StreamObject>,)>,
+    set_audio_object_volume_function: &'static DBusFunction<VolumeArgs, ()>,
+    set_audio_object_mute_function: &'static DBusFunction<MuteArgs, ()>,
 ) {
     gio::spawn_blocking(move || {
         let sources = audio_dbus_call::<AudioBox, (Vec<AudioObject>,), ()>(
@@ -337,7 +340,7 @@ pub fn object_changed_handler<
 >(
     audio_box: Arc<AudioBox>,
     ir: Event,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<(), (String,)>,
 ) -> bool {
     let source = audio_dbus_call::<AudioBox, (String,), ()>(audio_box.clone(), (), function);
     if source.is_none() {
@@ -23,6 +23,7 @@ use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
 
 use crate::components::{
     base::{card_entry::CardEntry, error_impl::ReSetErrorImpl, list_entry::ListEntry},
+    daemon::proxy::audio::{MuteArgs, VolumeArgs},
     utils::{create_dropdown_label_factory, set_combo_row_ellipsis, BASE, DBUS_PATH},
 };
 
@@ -38,7 +39,7 @@ use super::{
         TAudioStream, TAudioStreamImpl,
     },
     audio_functions::new_stream_entry,
-    audio_utils::audio_dbus_call,
+    audio_utils::{audio_dbus_call, configure_volume_slider},
 };
 
 pub fn setup_audio_box_callbacks<
@@ -119,7 +120,7 @@ pub fn populate_streams<
     AudioBoxImpl: TAudioBoxImpl<AudioObject, AudioEntry, AudioStream>,
 >(
     audio_box: Arc<AudioBox>,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<(), (Vec<StreamObject>,)>,
 ) {
     let audio_box_ref = audio_box.clone();
     gio::spawn_blocking(move || {
@@ -220,9 +221,9 @@ pub fn populate_audio_object_information<
 >(
     audio_box: Arc<AudioBox>,
     audio_objects: Vec<AudioObject>,
-    dropdown_function: &'static DBusFunction,
-    change_volume_function: &'static DBusFunction,
-    mute_function: &'static DBusFunction,
+    dropdown_function: &'static DBusFunction<(String,), (AudioObject,)>,
+    change_volume_function: &'static DBusFunction<VolumeArgs, ()>,
+    mute_function: &'static DBusFunction<MuteArgs, ()>,
 ) {
     glib::spawn_future(async move {
         glib::idle_add_once(move || {
@@ -246,6 +247,7 @@ pub fn populate_audio_object_information<
             let fraction = (*volume as f64 / 655.36).round();
             let percentage = (fraction).to_string() + "%";
             imp.volume_percentage().set_text(&percentage);
+            configure_volume_slider(imp.volume_slider());
             imp.volume_slider().set_value(*volume as f64);
             let list = imp.audio_object_list();
             let mut list = list.write().unwrap();
@@ -331,7 +333,7 @@ pub fn start_audio_box_listener<
 >(
     conn: Connection,
     source_box: Arc<AudioBox>,
-    get_default_name_function: &'static DBusFunction,
+    get_default_name_function: &'static DBusFunction<(), (String,)>,
     dummy_name: &'static str,
 ) -> Connection {
     // FUTURE TODO: make the failed logs generically sound -> deynamic output for both
@@ -1,6 +1,10 @@
+use re_set_lib::audio::audio_structures::Card;
+
+use crate::components::daemon::proxy::audio::LIST_CARDS;
+
 use super::audio_entry::DBusFunction;
 
-pub const GETCARDS: DBusFunction = DBusFunction {
-    function: "ListCards",
+pub const GETCARDS: DBusFunction<(), (Vec<Card>,)> = DBusFunction {
+    function: LIST_CARDS,
     error: "Failed to get list profiles",
 };
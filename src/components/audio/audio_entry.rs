@@ -17,10 +17,12 @@ use re_set_lib::audio::audio_structures::{TAudioObject, TAudioStreamObject};
 use crate::components::base::error::ReSetError;
 use crate::components::base::error_impl::ReSetErrorImpl;
 use crate::components::base::list_entry::ListEntry;
+use crate::components::daemon::proxy::audio::{MuteArgs, VolumeArgs};
+use crate::components::daemon::proxy::DBusMethod;
 use crate::components::utils::set_action_row_ellipsis;
 
 use super::audio_functions::refresh_default_audio_object;
-use super::audio_utils::audio_dbus_call;
+use super::audio_utils::{audio_dbus_call, configure_volume_slider};
 
 pub type AudioEntryMap<T> = Arc<RwLock<HashMap<u32, (Arc<ListEntry>, Arc<T>, String)>>>;
 pub type AudioStreamEntryMap<T> = Arc<RwLock<HashMap<u32, (Arc<ListEntry>, Arc<T>)>>>;
@@ -67,9 +69,9 @@ pub trait TAudioEntryImpl<AudioObject: TAudioObject> {
     fn volume_percentage(&self) -> &TemplateChild<Label>;
     fn audio_object(&self) -> Arc<RefCell<AudioObject>>;
     fn volume_time_stamp(&self) -> &RefCell<Option<SystemTime>>;
-    fn set_volume_fn(&self) -> &'static DBusFunction;
-    fn set_audio_object_fn(&self) -> &'static DBusFunction;
-    fn set_mute_fn(&self) -> &'static DBusFunction;
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()>;
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(String,), (AudioObject,)>;
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()>;
     fn icons(&self) -> &AudioIcons;
 }
 
@@ -85,9 +87,9 @@ pub trait TAudioStreamImpl<AudioObject: TAudioObject, StreamObject: TAudioStream
     fn stream_object(&self) -> Arc<RefCell<StreamObject>>;
     fn associated_audio_object(&self) -> Arc<RefCell<(u32, String)>>;
     fn volume_time_stamp(&self) -> &RefCell<Option<SystemTime>>;
-    fn set_volume_fn(&self) -> &'static DBusFunction;
-    fn set_audio_object_fn(&self) -> &'static DBusFunction;
-    fn set_mute_fn(&self) -> &'static DBusFunction;
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()>;
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(u32, u32), ()>;
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()>;
     fn icons(&self) -> &AudioIcons;
 }
 
@@ -96,8 +98,9 @@ pub struct AudioIcons {
     pub active: &'static str,
 }
 
-pub struct DBusFunction {
-    pub function: &'static str,
+/// A typed daemon method of the sink or source side, along with the error shown on failure.
+pub struct DBusFunction<I, O> {
+    pub function: DBusMethod<I, O>,
     pub error: &'static str,
 }
 
@@ -131,6 +134,7 @@ pub fn new_entry<
         let output_box_slider = reset_box.clone();
         let output_box_ref = reset_box.clone();
         imp.volume_percentage().set_text(&percentage);
+        configure_volume_slider(imp.volume_slider());
         imp.volume_slider().set_value(*volume as f64);
         imp.audio_object().replace(audio_object);
         imp.volume_slider()
@@ -173,9 +177,9 @@ pub fn new_entry<
             if button.is_active() {
                 let name = name.clone();
                 gio::spawn_blocking(move || {
-                    let result = audio_dbus_call::<AudioBox, (AudioObject,), (&String,)>(
+                    let result = audio_dbus_call::<AudioBox, (AudioObject,), (String,)>(
                         output_box_ref.clone(),
-                        (&name,),
+                        (name.to_string(),),
                         audio_object_fn,
                     );
                     if result.is_none() {
@@ -20,7 +20,7 @@ use super::{
     audio_entry::{
         TAudioBox, TAudioBoxImpl, TAudioEntry, TAudioEntryImpl, TAudioStream, TAudioStreamImpl,
     },
-    audio_utils::audio_dbus_call,
+    audio_utils::{audio_dbus_call, configure_volume_slider},
 };
 
 pub fn refresh_default_audio_object<
@@ -118,6 +118,7 @@ pub fn new_stream_entry<
         let fraction = (*volume as f64 / 655.36).round();
         let percentage = (fraction).to_string() + "%";
         imp.volume_percentage().set_text(&percentage);
+        configure_volume_slider(imp.volume_slider());
         imp.volume_slider().set_value(*volume as f64);
         imp.stream_object().replace(stream);
         {
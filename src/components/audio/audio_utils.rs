@@ -1,14 +1,15 @@
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use dbus::{
     arg::{AppendAll, ReadAll},
-    blocking::Connection,
     Error,
 };
+use gtk::{prelude::RangeExt, Scale};
 
 use crate::components::{
     base::error_impl::{show_error, ReSetErrorImpl},
-    utils::{AUDIO, BASE, DBUS_PATH},
+    config::frontend::frontend_config,
+    daemon::proxy::{DaemonCall, DaemonClient},
 };
 
 use super::audio_entry::DBusFunction;
@@ -16,19 +17,26 @@ use super::audio_entry::DBusFunction;
 pub fn audio_dbus_call<B, O, I>(
     source_box: Arc<B>,
     args: I,
-    function: &'static DBusFunction,
+    function: &'static DBusFunction<I, O>,
 ) -> Option<O>
 where
     O: ReadAll,
     I: AppendAll,
     B: ReSetErrorImpl + 'static,
 {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<O, Error> = proxy.method_call(AUDIO, function.function, args);
+    let res: Result<O, Error> = DaemonClient::new().call(&function.function, args);
     if res.is_err() {
         show_error::<B>(source_box.clone(), function.error);
         return None;
     }
     Some(res.unwrap())
 }
+
+/// Applies the volume range and step of the frontend config to a volume slider.
+pub fn configure_volume_slider(slider: &Scale) {
+    let config = frontend_config();
+    let adjustment = slider.adjustment();
+    adjustment.set_upper(config.max_volume_value());
+    adjustment.set_step_increment(config.volume_step_value());
+    adjustment.set_page_increment(config.volume_step_value());
+}
@@ -2,6 +2,6 @@ pub mod output_stream_entry;
 pub mod output_stream_entry_impl;
 pub mod source_box;
 pub mod source_box_impl;
-mod source_const;
+pub mod source_const;
 pub mod source_entry;
 pub mod source_entry_impl;
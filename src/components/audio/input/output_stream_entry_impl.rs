@@ -5,8 +5,9 @@ use std::cell::RefCell;
 use std::sync::Arc;
 use std::time::SystemTime;
 
-use crate::components::audio::audio_entry::{AudioIcons, TAudioStreamImpl};
+use crate::components::audio::audio_entry::{AudioIcons, DBusFunction, TAudioStreamImpl};
 use crate::components::audio::input::output_stream_entry;
+use crate::components::daemon::proxy::audio::{MuteArgs, VolumeArgs};
 use gtk::subclass::prelude::*;
 use gtk::{Button, CompositeTemplate, Label, Scale};
 
@@ -79,15 +80,15 @@ impl TAudioStreamImpl<Source, OutputStream> for OutputStreamEntry {
         &self.volume_time_stamp
     }
 
-    fn set_volume_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()> {
         &SETSTREAMVOLUME
     }
 
-    fn set_audio_object_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(u32, u32), ()> {
         &SETSTREAMOBJECT
     }
 
-    fn set_mute_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()> {
         &SETSTREAMMUTE
     }
 
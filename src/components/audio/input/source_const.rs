@@ -1,57 +1,64 @@
+use re_set_lib::audio::audio_structures::{OutputStream, Source};
+
 use crate::components::audio::audio_entry::{AudioIcons, DBusFunction};
+use crate::components::daemon::proxy::audio::{
+    MuteArgs, VolumeArgs, GET_DEFAULT_SOURCE, GET_DEFAULT_SOURCE_NAME, LIST_OUTPUT_STREAMS,
+    LIST_SOURCES, SET_DEFAULT_SOURCE, SET_OUTPUT_STREAM_MUTE, SET_OUTPUT_STREAM_VOLUME,
+    SET_SOURCE_MUTE, SET_SOURCE_OF_OUTPUT_STREAM, SET_SOURCE_VOLUME,
+};
 
 pub const ICONS: AudioIcons = AudioIcons {
     muted: "microphone-disabled-symbolic",
     active: "audio-input-microphone-symbolic",
 };
 
-pub const SETVOLUME: DBusFunction = DBusFunction {
-    function: "SetSourceVolume",
+pub const SETVOLUME: DBusFunction<VolumeArgs, ()> = DBusFunction {
+    function: SET_SOURCE_VOLUME,
     error: "Failed to set source volume",
 };
 
-pub const SETMUTE: DBusFunction = DBusFunction {
-    function: "SetSourceMute",
+pub const SETMUTE: DBusFunction<MuteArgs, ()> = DBusFunction {
+    function: SET_SOURCE_MUTE,
     error: "Failed to mute source",
 };
 
-pub const SETDEFAULT: DBusFunction = DBusFunction {
-    function: "SetDefaultSource",
+pub const SETDEFAULT: DBusFunction<(String,), (Source,)> = DBusFunction {
+    function: SET_DEFAULT_SOURCE,
     error: "Failed to set default source",
 };
 
-pub const GETDEFAULT: DBusFunction = DBusFunction {
-    function: "GetDefaultSource",
+pub const GETDEFAULT: DBusFunction<(), (Source,)> = DBusFunction {
+    function: GET_DEFAULT_SOURCE,
     error: "Failed to get default source",
 };
 
-pub const GETDEFAULTNAME: DBusFunction = DBusFunction {
-    function: "GetDefaultSourceName",
+pub const GETDEFAULTNAME: DBusFunction<(), (String,)> = DBusFunction {
+    function: GET_DEFAULT_SOURCE_NAME,
     error: "Failed to get default source name",
 };
 
-pub const GETOBJECTS: DBusFunction = DBusFunction {
-    function: "ListSources",
+pub const GETOBJECTS: DBusFunction<(), (Vec<Source>,)> = DBusFunction {
+    function: LIST_SOURCES,
     error: "Failed to list sources",
 };
 
-pub const GETSTREAMS: DBusFunction = DBusFunction {
-    function: "ListOutputStreams",
+pub const GETSTREAMS: DBusFunction<(), (Vec<OutputStream>,)> = DBusFunction {
+    function: LIST_OUTPUT_STREAMS,
     error: "Failed to list output streams",
 };
 
-pub const SETSTREAMVOLUME: DBusFunction = DBusFunction {
-    function: "SetOutputStreamVolume",
+pub const SETSTREAMVOLUME: DBusFunction<VolumeArgs, ()> = DBusFunction {
+    function: SET_OUTPUT_STREAM_VOLUME,
     error: "Failed to set output stream volume",
 };
 
-pub const SETSTREAMMUTE: DBusFunction = DBusFunction {
-    function: "SetOutputStreamMute",
+pub const SETSTREAMMUTE: DBusFunction<MuteArgs, ()> = DBusFunction {
+    function: SET_OUTPUT_STREAM_MUTE,
     error: "Failed to mute output stream",
 };
 
-pub const SETSTREAMOBJECT: DBusFunction = DBusFunction {
-    function: "SetSourceOfOutputStream",
+pub const SETSTREAMOBJECT: DBusFunction<(u32, u32), ()> = DBusFunction {
+    function: SET_SOURCE_OF_OUTPUT_STREAM,
     error: "Failed to set source of output stream",
 };
 
@@ -9,6 +9,7 @@ use gtk::subclass::prelude::*;
 use gtk::{Button, CheckButton, CompositeTemplate, Label, Scale};
 
 use crate::components::audio::audio_entry::{AudioIcons, DBusFunction, TAudioEntryImpl};
+use crate::components::daemon::proxy::audio::{MuteArgs, VolumeArgs};
 
 use super::source_const::{ICONS, SETDEFAULT, SETMUTE, SETVOLUME};
 use super::source_entry;
@@ -81,15 +82,15 @@ impl TAudioEntryImpl<Source> for SourceEntry {
         &self.volume_time_stamp
     }
 
-    fn set_volume_fn(&self) -> &'static DBusFunction {
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()> {
         &SETVOLUME
     }
 
-    fn set_audio_object_fn(&self) -> &'static DBusFunction {
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(String,), (Source,)> {
         &SETDEFAULT
     }
 
-    fn set_mute_fn(&self) -> &'static DBusFunction {
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()> {
         &SETMUTE
     }
 
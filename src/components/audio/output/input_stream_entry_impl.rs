@@ -8,7 +8,8 @@ use std::time::SystemTime;
 use gtk::subclass::prelude::*;
 use gtk::{Button, CompositeTemplate, Label, Scale};
 
-use crate::components::audio::audio_entry::{AudioIcons, TAudioStreamImpl};
+use crate::components::audio::audio_entry::{AudioIcons, DBusFunction, TAudioStreamImpl};
+use crate::components::daemon::proxy::audio::{MuteArgs, VolumeArgs};
 
 use super::input_stream_entry;
 use super::sink_const::{ICONS, SETSTREAMMUTE, SETSTREAMOBJECT, SETSTREAMVOLUME};
@@ -80,15 +81,15 @@ impl TAudioStreamImpl<Sink, InputStream> for InputStreamEntry {
         &self.volume_time_stamp
     }
 
-    fn set_volume_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()> {
         &SETSTREAMVOLUME
     }
 
-    fn set_audio_object_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(u32, u32), ()> {
         &SETSTREAMOBJECT
     }
 
-    fn set_mute_fn(&self) -> &'static crate::components::audio::audio_entry::DBusFunction {
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()> {
         &SETSTREAMMUTE
     }
 
@@ -2,6 +2,6 @@ pub mod input_stream_entry;
 pub mod input_stream_entry_impl;
 pub mod sink_box;
 pub mod sink_box_impl;
-mod sink_const;
+pub mod sink_const;
 pub mod sink_entry;
 pub mod sink_entry_impl;
@@ -17,6 +17,7 @@ use crate::components::audio::audio_box_utils::setup_audio_box_callbacks;
 use crate::components::audio::audio_box_utils::start_audio_box_listener;
 use crate::components::audio::audio_entry::TAudioBox;
 use crate::components::base::error_impl::ReSetErrorImpl;
+use crate::components::plugin::extension::{insert_extensions, ExtensionPoint};
 
 use super::input_stream_entry::InputStreamEntry;
 use super::sink_box_impl;
@@ -51,6 +52,13 @@ impl TAudioBox<super::sink_box_impl::SinkBox> for SinkBox {
 
 impl SinkBox {
     pub fn new() -> Self {
+        let obj = Self::without_extensions();
+        insert_extensions(ExtensionPoint::SinkBox, &obj.imp().reset_sink_main_page);
+        obj
+    }
+
+    /// Without the page extensions, which only the main window shows.
+    pub fn without_extensions() -> Self {
         let mut obj: Self = Object::builder().build();
         setup_audio_box_callbacks::<
             Sink,
@@ -26,6 +26,8 @@ type SinkMap = Arc<RwLock<HashMap<String, (u32, String)>>>;
 #[derive(Default, CompositeTemplate)]
 #[template(resource = "/org/Xetibo/ReSet/resetAudioOutput.ui")]
 pub struct SinkBox {
+    #[template_child]
+    pub reset_sink_main_page: TemplateChild<Box>,
     #[template_child]
     pub reset_sinks_row: TemplateChild<ActionRow>,
     #[template_child]
@@ -1,57 +1,64 @@
+use re_set_lib::audio::audio_structures::{InputStream, Sink};
+
 use crate::components::audio::audio_entry::{AudioIcons, DBusFunction};
+use crate::components::daemon::proxy::audio::{
+    MuteArgs, VolumeArgs, GET_DEFAULT_SINK, GET_DEFAULT_SINK_NAME, LIST_INPUT_STREAMS, LIST_SINKS,
+    SET_DEFAULT_SINK, SET_INPUT_STREAM_MUTE, SET_INPUT_STREAM_VOLUME, SET_SINK_MUTE,
+    SET_SINK_OF_INPUT_STREAM, SET_SINK_VOLUME,
+};
 
 pub const ICONS: AudioIcons = AudioIcons {
     muted: "audio-volume-muted-symbolic",
     active: "audio-volume-high-symbolic",
 };
 
-pub const SETVOLUME: DBusFunction = DBusFunction {
-    function: "SetSinkVolume",
+pub const SETVOLUME: DBusFunction<VolumeArgs, ()> = DBusFunction {
+    function: SET_SINK_VOLUME,
     error: "Failed to set sink volume",
 };
 
-pub const SETMUTE: DBusFunction = DBusFunction {
-    function: "SetSinkMute",
+pub const SETMUTE: DBusFunction<MuteArgs, ()> = DBusFunction {
+    function: SET_SINK_MUTE,
     error: "Failed to mute sink",
 };
 
-pub const SETDEFAULT: DBusFunction = DBusFunction {
-    function: "SetDefaultSink",
+pub const SETDEFAULT: DBusFunction<(String,), (Sink,)> = DBusFunction {
+    function: SET_DEFAULT_SINK,
     error: "Failed to set default sink",
 };
 
-pub const GETDEFAULT: DBusFunction = DBusFunction {
-    function: "GetDefaultSink",
+pub const GETDEFAULT: DBusFunction<(), (Sink,)> = DBusFunction {
+    function: GET_DEFAULT_SINK,
     error: "Failed to get default sink",
 };
 
-pub const GETDEFAULTNAME: DBusFunction = DBusFunction {
-    function: "GetDefaultSinkName",
+pub const GETDEFAULTNAME: DBusFunction<(), (String,)> = DBusFunction {
+    function: GET_DEFAULT_SINK_NAME,
     error: "Failed to get default sink name",
 };
 
-pub const GETOBJECTS: DBusFunction = DBusFunction {
-    function: "ListSinks",
+pub const GETOBJECTS: DBusFunction<(), (Vec<Sink>,)> = DBusFunction {
+    function: LIST_SINKS,
     error: "Failed to list sinks",
 };
 
-pub const GETSTREAMS: DBusFunction = DBusFunction {
-    function: "ListInputStreams",
+pub const GETSTREAMS: DBusFunction<(), (Vec<InputStream>,)> = DBusFunction {
+    function: LIST_INPUT_STREAMS,
     error: "Failed to list input streams",
 };
 
-pub const SETSTREAMVOLUME: DBusFunction = DBusFunction {
-    function: "SetInputStreamVolume",
+pub const SETSTREAMVOLUME: DBusFunction<VolumeArgs, ()> = DBusFunction {
+    function: SET_INPUT_STREAM_VOLUME,
     error: "Failed to set input stream volume",
 };
 
-pub const SETSTREAMMUTE: DBusFunction = DBusFunction {
-    function: "SetInputStreamMute",
+pub const SETSTREAMMUTE: DBusFunction<MuteArgs, ()> = DBusFunction {
+    function: SET_INPUT_STREAM_MUTE,
     error: "Failed to mute input stream",
 };
 
-pub const SETSTREAMOBJECT: DBusFunction = DBusFunction {
-    function: "SetSinkOfInputStream",
+pub const SETSTREAMOBJECT: DBusFunction<(u32, u32), ()> = DBusFunction {
+    function: SET_SINK_OF_INPUT_STREAM,
     error: "Failed to set sink of input stream",
 };
 
@@ -7,6 +7,7 @@ use std::time::SystemTime;
 
 use crate::components::audio::audio_entry::{AudioIcons, DBusFunction, TAudioEntryImpl};
 use crate::components::audio::output::sink_entry;
+use crate::components::daemon::proxy::audio::{MuteArgs, VolumeArgs};
 use gtk::subclass::prelude::*;
 use gtk::{Button, CheckButton, CompositeTemplate, Label, Scale};
 
@@ -80,15 +81,15 @@ impl TAudioEntryImpl<Sink> for SinkEntry {
         &self.volume_time_stamp
     }
 
-    fn set_volume_fn(&self) -> &'static DBusFunction {
+    fn set_volume_fn(&self) -> &'static DBusFunction<VolumeArgs, ()> {
         &SETVOLUME
     }
 
-    fn set_audio_object_fn(&self) -> &'static DBusFunction {
+    fn set_audio_object_fn(&self) -> &'static DBusFunction<(String,), (Sink,)> {
         &SETDEFAULT
     }
 
-    fn set_mute_fn(&self) -> &'static DBusFunction {
+    fn set_mute_fn(&self) -> &'static DBusFunction<MuteArgs, ()> {
         &SETMUTE
     }
 
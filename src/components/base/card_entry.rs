@@ -1,9 +1,5 @@
-use std::time::Duration;
-
 use adw::glib::Object;
 use adw::prelude::{ComboRowExt, PreferencesRowExt};
-use dbus::blocking::Connection;
-use dbus::Error;
 use glib::clone;
 use glib::prelude::Cast;
 use glib::subclass::types::ObjectSubclassIsExt;
@@ -13,7 +9,7 @@ use components::utils::create_dropdown_label_factory;
 use re_set_lib::audio::audio_structures::Card;
 
 use crate::components;
-use crate::components::utils::{AUDIO, BASE, DBUS_PATH};
+use crate::components::daemon::proxy::{audio::AudioProxy, DaemonClient};
 
 use super::card_entry_impl;
 
@@ -65,13 +61,7 @@ impl CardEntry {
 
 fn set_card_profile_of_device(device_index: u32, profile_name: String) -> bool {
     gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let _: Result<(), Error> = proxy.method_call(
-            AUDIO,
-            "SetCardProfileOfDevice",
-            (device_index, profile_name),
-        );
+        let _ = DaemonClient::new().set_card_profile_of_device(device_index, profile_name);
     });
     true
 }
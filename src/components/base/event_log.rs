@@ -0,0 +1,129 @@
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Older events are dropped once the log is longer than this.
+const MAX_EVENTS: usize = 200;
+
+static EVENTS: Mutex<Vec<LoggedEvent>> = Mutex::new(Vec::new());
+/// Set once a window listens for events, the command line only keeps the log.
+static NOTIFY_LISTENERS: AtomicBool = AtomicBool::new(false);
+
+type EventListener = Box<dyn Fn(&LoggedEvent)>;
+
+thread_local! {
+    // listeners update widgets, so they only run on the main thread
+    static LISTENERS: RefCell<Vec<(EventListenerId, EventListener)>> =
+        const { RefCell::new(Vec::new()) };
+    static NEXT_LISTENER_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returned by [`connect_event_logged`] to remove the listener again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventListenerId(usize);
+
+/// A failed daemon call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// Seconds since the unix epoch.
+    pub timestamp: i64,
+    /// Interface and name of the D-Bus method, e.g. `org.Xetibo.ReSet.Audio.SetSinkVolume`.
+    pub method: String,
+    pub message: String,
+}
+
+impl LoggedEvent {
+    pub fn new(method: &str, message: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() as i64);
+        Self {
+            timestamp,
+            method: method.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Short name of the method, e.g. `SetSinkVolume`.
+    pub fn method_name(&self) -> &str {
+        self.method.rsplit('.').next().unwrap_or(&self.method)
+    }
+
+    /// Local time of the event, e.g. `2024-03-01 14:05:09`.
+    pub fn time(&self) -> String {
+        glib::DateTime::from_unix_local(self.timestamp)
+            .and_then(|time| time.format("%Y-%m-%d %H:%M:%S"))
+            .map_or_else(|_| self.timestamp.to_string(), String::from)
+    }
+
+    /// The event as a single line, as copied to the clipboard.
+    pub fn to_line(&self) -> String {
+        format!("[{}] {}: {}", self.time(), self.method, self.message)
+    }
+}
+
+/// Adds a failed daemon call to the log, can be called from any thread.
+/// The window is notified on the main thread.
+pub fn record_daemon_error(method: &str, message: &str) {
+    let event = LoggedEvent::new(method, message);
+    push_event(event.clone());
+    if !NOTIFY_LISTENERS.load(Ordering::SeqCst) {
+        return;
+    }
+    glib::idle_add_once(move || {
+        LISTENERS.with(|listeners| {
+            for (_, listener) in listeners.borrow().iter() {
+                listener(&event);
+            }
+        });
+    });
+}
+
+fn push_event(event: LoggedEvent) {
+    let mut events = EVENTS.lock().unwrap_or_else(PoisonError::into_inner);
+    events.push(event);
+    if events.len() > MAX_EVENTS {
+        let dropped = events.len() - MAX_EVENTS;
+        events.drain(..dropped);
+    }
+}
+
+/// Calls the listener on the main thread for every event logged from now on,
+/// until it is removed with [`disconnect_event_logged`].
+pub fn connect_event_logged(listener: impl Fn(&LoggedEvent) + 'static) -> EventListenerId {
+    let id = EventListenerId(NEXT_LISTENER_ID.with(|next| next.replace(next.get() + 1)));
+    LISTENERS.with(|listeners| listeners.borrow_mut().push((id, Box::new(listener))));
+    NOTIFY_LISTENERS.store(true, Ordering::SeqCst);
+    id
+}
+
+pub fn disconnect_event_logged(id: EventListenerId) {
+    LISTENERS.with(|listeners| {
+        let mut listeners = listeners.borrow_mut();
+        listeners.retain(|(listener_id, _)| *listener_id != id);
+        NOTIFY_LISTENERS.store(!listeners.is_empty(), Ordering::SeqCst);
+    });
+}
+
+/// The logged events, the newest first.
+pub fn logged_events() -> Vec<LoggedEvent> {
+    let events = EVENTS.lock().unwrap_or_else(PoisonError::into_inner);
+    events.iter().rev().cloned().collect()
+}
+
+pub fn clear_event_log() {
+    EVENTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// All events as text, one per line and the newest first.
+pub fn event_log_text(events: &[LoggedEvent]) -> String {
+    events
+        .iter()
+        .map(LoggedEvent::to_line)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,22 +1,26 @@
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
     },
     thread,
     time::Duration,
 };
 
-use dbus::{blocking::Connection, Error};
+use dbus::blocking::Connection;
 use gtk::gio;
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
 
 use crate::components::{
-    audio::input::source_box::{start_source_box_listener, SourceBox},
-    audio::output::sink_box::{start_sink_box_listener, SinkBox},
-    utils::{BASE, DBUS_PATH, WIRELESS},
+    audio::input::source_box::{populate_sources, start_source_box_listener, SourceBox},
+    audio::output::sink_box::{populate_sinks, start_sink_box_listener, SinkBox},
+    daemon::proxy::{network::NetworkProxy, DaemonClient},
+    plugin::manager::{call_hook, PluginHooks},
 };
 
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub enum Position {
     Connectivity,
     Wifi,
@@ -24,11 +28,78 @@ pub enum Position {
     Audio,
     AudioOutput,
     AudioInput,
+    Plugins,
     Custom(String),
     #[default]
     Home,
 }
 
+impl Position {
+    /// Parses a page name as used on the command line, e.g. `wifi` or `custom:Monitors`.
+    pub fn from_page_name(page: &str) -> Option<Self> {
+        if let Some(name) = page.strip_prefix("custom:") {
+            if name.is_empty() {
+                return None;
+            }
+            return Some(Position::Custom(String::from(name)));
+        }
+        match page.to_lowercase().as_str() {
+            "home" => Some(Position::Home),
+            "connectivity" => Some(Position::Connectivity),
+            "wifi" => Some(Position::Wifi),
+            "bluetooth" => Some(Position::Bluetooth),
+            "audio" => Some(Position::Audio),
+            "output" | "audio-output" => Some(Position::AudioOutput),
+            "input" | "audio-input" => Some(Position::AudioInput),
+            "plugins" => Some(Position::Plugins),
+            _ => None,
+        }
+    }
+
+    /// Inverse of [`Position::from_page_name`].
+    pub fn page_name(&self) -> String {
+        let name = match self {
+            Position::Home => "home",
+            Position::Connectivity => "connectivity",
+            Position::Wifi => "wifi",
+            Position::Bluetooth => "bluetooth",
+            Position::Audio => "audio",
+            Position::AudioOutput => "output",
+            Position::AudioInput => "input",
+            Position::Plugins => "plugins",
+            Position::Custom(name) => return format!("custom:{}", name),
+        };
+        String::from(name)
+    }
+
+    /// Name of the sidebar entry that opens this position.
+    pub fn sidebar_name(&self) -> Option<&str> {
+        match self {
+            Position::Home => Some("Home"),
+            Position::Connectivity => Some("Connectivity"),
+            Position::Wifi => Some("WiFi"),
+            Position::Bluetooth => Some("Bluetooth"),
+            Position::Audio => Some("Audio"),
+            Position::AudioOutput => Some("Output"),
+            Position::AudioInput => Some("Input"),
+            Position::Plugins => Some("Plugins"),
+            Position::Custom(name) => Some(name.as_str()),
+        }
+    }
+}
+
+/// Windows with a running network or bluetooth listener. The daemon only has one listener
+/// of each kind, which is stopped once the last window is done with it.
+static NETWORK_LISTENER_USERS: AtomicUsize = AtomicUsize::new(0);
+static BLUETOOTH_LISTENER_USERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns true if the last user of a daemon listener released it.
+fn release_listener(users: &AtomicUsize) -> bool {
+    users.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+        count.checked_sub(1)
+    }) == Ok(1)
+}
+
 #[derive(Default)]
 pub struct Listeners {
     pub wifi_disabled: AtomicBool,
@@ -36,18 +107,37 @@ pub struct Listeners {
     pub bluetooth_listener: AtomicBool,
     pub bluetooth_scan_requested: AtomicBool,
     pub pulse_listener: AtomicBool,
+    /// Cleared once the window is destroyed, which ends the watcher of the daemon name.
+    pub daemon_watcher: AtomicBool,
+    /// Hooks of the plugin page that is currently shown.
+    pub plugin_hooks: Mutex<PluginHooks>,
+    /// Flags of the listeners the current plugin page registered.
+    pub plugin_listeners: Mutex<Vec<Arc<AtomicBool>>>,
 }
 
 impl Listeners {
+    /// Marks the network listener of this window as running, returns false if it already was.
+    pub fn acquire_network_listener(&self) -> bool {
+        if self.wifi_listener.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        NETWORK_LISTENER_USERS.fetch_add(1, Ordering::SeqCst);
+        true
+    }
+
+    /// Marks the network listener of this window as stopped,
+    /// returns true if no other window uses the listener of the daemon.
+    pub fn release_network_listener(&self) -> bool {
+        self.wifi_listener.swap(false, Ordering::SeqCst)
+            && release_listener(&NETWORK_LISTENER_USERS)
+    }
+
     pub fn stop_network_listener(&self) {
-        if !self.wifi_listener.load(Ordering::SeqCst) {
+        if !self.release_network_listener() {
             return;
         }
-        self.wifi_listener.store(false, Ordering::SeqCst);
         thread::spawn(|| {
-            let conn = Connection::new_session().unwrap();
-            let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-            let _: Result<(bool,), Error> = proxy.method_call(WIRELESS, "StopNetworkListener", ());
+            let _ = DaemonClient::new().stop_network_listener();
         });
     }
 
@@ -58,27 +148,96 @@ impl Listeners {
     pub fn stop_bluetooth_listener(&self) {
         self.bluetooth_listener.store(false, Ordering::SeqCst);
     }
+
+    /// Marks the bluetooth listener of this window as running, returns false if it already was.
+    pub fn acquire_bluetooth_listener(&self) -> bool {
+        if self.bluetooth_listener.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        BLUETOOTH_LISTENER_USERS.fetch_add(1, Ordering::SeqCst);
+        true
+    }
+
+    /// Called by the listener loop once it stopped,
+    /// returns true if no other window uses the listener of the daemon.
+    pub fn release_bluetooth_listener(&self) -> bool {
+        release_listener(&BLUETOOTH_LISTENER_USERS)
+    }
+
+    pub fn enter_plugin_page(&self, hooks: PluginHooks) {
+        if let Some(enter) = hooks.enter {
+            call_hook("frontend_enter", enter, hooks.take_panic);
+        }
+        *self
+            .plugin_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = hooks;
+    }
+
+    /// Returns the flag of a new plugin listener, which is cleared once the page is left.
+    pub fn add_plugin_listener(&self) -> Arc<AtomicBool> {
+        let active = Arc::new(AtomicBool::new(true));
+        self.plugin_listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(active.clone());
+        active
+    }
+
+    pub fn leave_plugin_page(&self) {
+        for listener in self
+            .plugin_listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+        {
+            listener.store(false, Ordering::SeqCst);
+        }
+        let hooks = std::mem::take(
+            &mut *self
+                .plugin_hooks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if let Some(leave) = hooks.leave {
+            call_hook("frontend_leave", leave, hooks.take_panic);
+        }
+    }
 }
 
+/// Starts the audio listener and populates the given boxes once it is registered,
+/// this ensures that no events are missed between populating and listening.
 pub fn start_audio_listener(
     listeners: Arc<Listeners>,
     sink_box: Option<Arc<SinkBox>>,
     source_box: Option<Arc<SourceBox>>,
 ) {
     gio::spawn_blocking(move || {
-        let mut conn = Connection::new_session().unwrap();
         if listeners.pulse_listener.load(Ordering::SeqCst) {
+            populate_audio_boxes(sink_box, source_box);
             return;
         }
+        let mut conn = match Connection::new_session() {
+            Ok(conn) => conn,
+            Err(_) => {
+                ERROR!(
+                    "Could not connect to the session bus for the audio listener",
+                    ErrorLevel::PartialBreakage
+                );
+                populate_audio_boxes(sink_box, source_box);
+                return;
+            }
+        };
 
-        if let Some(sink_box) = sink_box {
+        if let Some(sink_box) = sink_box.clone() {
             conn = start_sink_box_listener(conn, sink_box);
         }
-        if let Some(source_box) = source_box {
+        if let Some(source_box) = source_box.clone() {
             conn = start_source_box_listener(conn, source_box);
         }
 
         listeners.pulse_listener.store(true, Ordering::SeqCst);
+        populate_audio_boxes(sink_box, source_box);
 
         loop {
             let _ = conn.process(Duration::from_millis(1000));
@@ -88,3 +247,12 @@ pub fn start_audio_listener(
         }
     });
 }
+
+fn populate_audio_boxes(sink_box: Option<Arc<SinkBox>>, source_box: Option<Arc<SourceBox>>) {
+    if let Some(sink_box) = sink_box {
+        populate_sinks(sink_box);
+    }
+    if let Some(source_box) = source_box {
+        populate_sources(source_box);
+    }
+}
@@ -7,7 +7,7 @@ use adw::prelude::{ComboRowExt, PreferencesGroupExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
 use dbus::blocking::Connection;
 use dbus::message::SignalArgs;
-use dbus::{Error, Path};
+use dbus::Path;
 use glib::prelude::Cast;
 use glib::property::PropertySet;
 use glib::{clone, ControlFlow};
@@ -17,7 +17,7 @@ use gtk::{gio, StringObject};
 use re_set_lib::{
     bluetooth::bluetooth_structures::{BluetoothAdapter, BluetoothDevice},
     signals::{BluetoothDeviceAdded, BluetoothDeviceChanged, BluetoothDeviceRemoved},
-    ERROR
+    ERROR,
 };
 
 #[cfg(debug_assertions)]
@@ -27,7 +27,9 @@ use crate::components::base::error_impl::{show_error, ReSetErrorImpl};
 use crate::components::base::utils::Listeners;
 use crate::components::bluetooth::bluetooth_box_impl;
 use crate::components::bluetooth::bluetooth_entry::BluetoothEntry;
-use crate::components::utils::{BASE, BLUETOOTH, DBUS_PATH};
+use crate::components::daemon::proxy::{bluetooth::BluetoothProxy, DaemonClient};
+use crate::components::plugin::extension::{insert_extensions, ExtensionPoint};
+use crate::components::utils::{BASE, DBUS_PATH};
 
 use super::bluetooth_event_handlers::{
     device_added_handler, device_changed_handler, device_removed_handler,
@@ -52,6 +54,16 @@ impl ReSetErrorImpl for BluetoothBox {
 
 impl BluetoothBox {
     pub fn new(listeners: Arc<Listeners>) -> Arc<Self> {
+        let obj = Self::without_extensions(listeners);
+        insert_extensions(
+            ExtensionPoint::BluetoothBox,
+            &obj.imp().reset_bluetooth_main_page,
+        );
+        obj
+    }
+
+    /// Builds the box without page extensions, as used by the quick settings.
+    pub fn without_extensions(listeners: Arc<Listeners>) -> Arc<Self> {
         let obj: Arc<Self> = Arc::new(Object::builder().build());
         setup_callbacks(listeners, obj)
     }
@@ -301,9 +313,17 @@ pub fn start_bluetooth_listener(listeners: Arc<Listeners>, bluetooth_box: Arc<Bl
         let device_changed_box = bluetooth_box.clone();
         let loop_box = bluetooth_box.clone();
 
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let res: Result<(), Error> = proxy.method_call(BLUETOOTH, "StartBluetoothListener", ());
+        let conn = match Connection::new_session() {
+            Ok(conn) => conn,
+            Err(_) => {
+                show_error::<BluetoothBox>(
+                    bluetooth_box.clone(),
+                    "Failed to start bluetooth listener",
+                );
+                return;
+            }
+        };
+        let res = DaemonClient::new().start_bluetooth_listener();
         if res.is_err() {
             show_error::<BluetoothBox>(bluetooth_box.clone(), "Failed to start bluetooth listener");
         }
@@ -352,14 +372,16 @@ pub fn start_bluetooth_listener(listeners: Arc<Listeners>, bluetooth_box: Arc<Bl
             return;
         }
 
-        listeners.bluetooth_listener.store(true, Ordering::SeqCst);
+        // another loop of this window may have started since the check above
+        if !listeners.acquire_bluetooth_listener() {
+            return;
+        }
         let time = SystemTime::now();
         let listener_active = true;
 
         bluetooth_listener_loop(
             &conn,
             listeners,
-            proxy,
             bluetooth_box,
             loop_box,
             listener_active,
@@ -371,7 +393,6 @@ pub fn start_bluetooth_listener(listeners: Arc<Listeners>, bluetooth_box: Arc<Bl
 fn bluetooth_listener_loop(
     conn: &Connection,
     listeners: Arc<Listeners>,
-    proxy: dbus::blocking::Proxy<'_, &Connection>,
     bluetooth_box: Arc<BluetoothBox>,
     loop_box: Arc<BluetoothBox>,
     mut listener_active: bool,
@@ -380,7 +401,12 @@ fn bluetooth_listener_loop(
     loop {
         let _ = conn.process(Duration::from_millis(1000));
         if !listeners.bluetooth_listener.load(Ordering::SeqCst) {
-            let res: Result<(), Error> = proxy.method_call(BLUETOOTH, "StopBluetoothListener", ());
+            // another window may still use the listener of the daemon
+            let res = if listeners.release_bluetooth_listener() {
+                DaemonClient::new().stop_bluetooth_listener()
+            } else {
+                Ok(())
+            };
             if res.is_err() {
                 show_error::<BluetoothBox>(
                     bluetooth_box.clone(),
@@ -404,7 +430,7 @@ fn bluetooth_listener_loop(
                         .set_sensitive(true);
                 });
             });
-            let res: Result<(), Error> = proxy.method_call(BLUETOOTH, "StopBluetoothScan", ());
+            let res = DaemonClient::new().stop_bluetooth_scan();
             if res.is_err() {
                 show_error::<BluetoothBox>(
                     bluetooth_box.clone(),
@@ -421,7 +447,7 @@ fn bluetooth_listener_loop(
                 .bluetooth_scan_requested
                 .store(false, Ordering::SeqCst);
             listener_active = true;
-            let res: Result<(), Error> = proxy.method_call(BLUETOOTH, "StartBluetoothListener", ());
+            let res = DaemonClient::new().start_bluetooth_listener();
             if res.is_err() {
                 show_error::<BluetoothBox>(
                     bluetooth_box.clone(),
@@ -438,34 +464,25 @@ fn bluetooth_listener_loop(
 }
 
 fn get_bluetooth_devices(bluetooth_box: Arc<BluetoothBox>) -> Vec<BluetoothDevice> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(Vec<BluetoothDevice>,), Error> =
-        proxy.method_call(BLUETOOTH, "GetBluetoothDevices", ());
+    let res = DaemonClient::new().get_bluetooth_devices();
     if res.is_err() {
         show_error::<BluetoothBox>(bluetooth_box.clone(), "Failed to get bluetooth devices");
         return Vec::new();
     }
-    res.unwrap().0
+    res.unwrap()
 }
 
 fn get_bluetooth_adapters(bluetooth_box: Arc<BluetoothBox>) -> Vec<BluetoothAdapter> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(Vec<BluetoothAdapter>,), Error> =
-        proxy.method_call(BLUETOOTH, "GetBluetoothAdapters", ());
+    let res = DaemonClient::new().get_bluetooth_adapters();
     if res.is_err() {
         show_error::<BluetoothBox>(bluetooth_box.clone(), "Failed to get bluetooth adapters");
         return Vec::new();
     }
-    res.unwrap().0
+    res.unwrap()
 }
 
 fn set_bluetooth_adapter(path: Path<'static>, bluetooth_box: Arc<BluetoothBox>) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(Path<'static>,), Error> =
-        proxy.method_call(BLUETOOTH, "SetBluetoothAdapter", (path,));
+    let res = DaemonClient::new().set_bluetooth_adapter(path);
     if res.is_err() {
         show_error::<BluetoothBox>(bluetooth_box.clone(), "Failed to set bluetooth adapter");
     }
@@ -476,13 +493,7 @@ fn set_bluetooth_adapter_visibility(
     visible: bool,
     bluetooth_box: Arc<BluetoothBox>,
 ) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(bool,), Error> = proxy.method_call(
-        BLUETOOTH,
-        "SetBluetoothAdapterDiscoverability",
-        (path, visible),
-    );
+    let res = DaemonClient::new().set_bluetooth_adapter_discoverability(path, visible);
     if res.is_err() {
         show_error::<BluetoothBox>(
             bluetooth_box.clone(),
@@ -496,10 +507,7 @@ fn set_bluetooth_adapter_pairability(
     visible: bool,
     bluetooth_box: Arc<BluetoothBox>,
 ) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(bool,), Error> =
-        proxy.method_call(BLUETOOTH, "SetBluetoothAdapterPairability", (path, visible));
+    let res = DaemonClient::new().set_bluetooth_adapter_pairability(path, visible);
     if res.is_err() {
         show_error::<BluetoothBox>(
             bluetooth_box.clone(),
@@ -513,13 +521,10 @@ fn set_adapter_enabled(
     enabled: bool,
     bluetooth_box: Arc<BluetoothBox>,
 ) -> bool {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let result: Result<(bool,), Error> =
-        proxy.method_call(BLUETOOTH, "SetBluetoothAdapterEnabled", (path, enabled));
+    let result = DaemonClient::new().set_bluetooth_adapter_enabled(path, enabled);
     if result.is_err() {
         show_error::<BluetoothBox>(bluetooth_box.clone(), "Failed to enable bluetooth adapter");
         return false;
     }
-    result.unwrap().0
+    result.unwrap()
 }
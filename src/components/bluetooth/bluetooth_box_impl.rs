@@ -22,6 +22,8 @@ pub struct BluetoothBox {
     #[template_child]
     pub reset_bluetooth_switch: TemplateChild<Switch>,
     #[template_child]
+    pub reset_bluetooth_main_page: TemplateChild<gtk::Box>,
+    #[template_child]
     pub reset_bluetooth_available_devices: TemplateChild<PreferencesGroup>,
     #[template_child]
     pub reset_bluetooth_saved_devices: TemplateChild<PreferencesGroup>,
@@ -1,16 +1,16 @@
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
 
 use crate::components::base::error_impl::show_error;
 use crate::components::bluetooth::bluetooth_entry_impl;
-use crate::components::utils::{BASE, BLUETOOTH, DBUS_PATH};
+use crate::components::daemon::proxy::{bluetooth::BluetoothProxy, DaemonClient};
+use crate::components::window::reset_window::show_undo_toast;
 use adw::glib::Object;
 use adw::prelude::{ActionRowExt, PreferencesRowExt};
 use adw::ActionRow;
-use dbus::blocking::Connection;
-use dbus::{Error, Path};
+use dbus::Path;
 use glib::subclass::prelude::ObjectSubclassIsExt;
+use gtk::gio::prelude::ApplicationExtManual;
 use gtk::prelude::{ButtonExt, ListBoxRowExt, WidgetExt};
 use gtk::{gio, Align, Button, GestureClick, Image, Label};
 use re_set_lib::bluetooth::bluetooth_structures::BluetoothDevice;
@@ -61,11 +61,16 @@ impl BluetoothEntry {
         entry_imp
             .remove_device_button
             .borrow()
-            .connect_clicked(move |_| {
-                let imp = entry_ref_remove.imp();
-                remove_device_pairing(
-                    imp.bluetooth_device.borrow().path.clone(),
-                    bluetooth_box.clone(),
+            .connect_clicked(move |button| {
+                let entry = entry_ref_remove.clone();
+                let bluetooth_box = bluetooth_box.clone();
+                entry.set_visible(false);
+                let undo_entry = entry.clone();
+                show_undo_toast(
+                    button,
+                    &format!("Removed {}", entry.imp().bluetooth_device.borrow().alias),
+                    move || undo_entry.set_visible(true),
+                    move || remove_device_pairing(entry, bluetooth_box),
                 );
             });
         let gesture = GestureClick::new();
@@ -92,10 +97,7 @@ impl BluetoothEntry {
 
 fn connect_to_device(entry: Arc<BluetoothEntry>, path: Path<'static>) {
     gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let res: Result<(bool,), Error> =
-            proxy.method_call(BLUETOOTH, "ConnectToBluetoothDevice", (path,));
+        let res = DaemonClient::new().connect_to_bluetooth_device(path);
         glib::spawn_future(async move {
             glib::idle_add_once(move || {
                 if res.is_err() {
@@ -132,10 +134,7 @@ fn connect_to_device(entry: Arc<BluetoothEntry>, path: Path<'static>) {
 
 fn disconnect_from_device(entry: Arc<BluetoothEntry>, path: Path<'static>) {
     gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let res: Result<(bool,), Error> =
-            proxy.method_call(BLUETOOTH, "DisconnectFromBluetoothDevice", (path,));
+        let res = DaemonClient::new().disconnect_from_bluetooth_device(path);
         glib::spawn_future(async move {
             glib::idle_add_once(move || {
                 let imp = entry.imp();
@@ -153,14 +152,18 @@ fn disconnect_from_device(entry: Arc<BluetoothEntry>, path: Path<'static>) {
     });
 }
 
-fn remove_device_pairing(path: Path<'static>, wifi_box: Arc<BluetoothBox>) {
-    gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let res: Result<(bool,), Error> =
-            proxy.method_call(BLUETOOTH, "RemoveDevicePairing", (path,));
-        if res.is_err() {
-            show_error::<BluetoothBox>(wifi_box.clone(), "Failed to remove device pairing");
+fn remove_device_pairing(entry: Arc<BluetoothEntry>, bluetooth_box: Arc<BluetoothBox>) {
+    let path = entry.imp().bluetooth_device.borrow().path.clone();
+    // keeps ReSet running until the daemon answered, the window may be closing
+    let hold = gio::Application::default().map(|app| app.hold());
+    glib::spawn_future_local(async move {
+        let res =
+            gio::spawn_blocking(move || DaemonClient::new().remove_device_pairing(path)).await;
+        // the removed device is dropped by the device listener, the entry stays hidden until then
+        if !matches!(res, Ok(Ok(true))) {
+            entry.set_visible(true);
+            show_error::<BluetoothBox>(bluetooth_box, "Failed to remove device pairing");
         }
+        drop(hold);
     });
 }
@@ -0,0 +1,311 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{PoisonError, RwLock};
+use std::time::Duration;
+
+use gtk::gio;
+use gtk::gio::prelude::{FileExt, FileMonitorExt};
+use re_set_lib::{create_config_directory, ERROR};
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+use toml::{Table, Value};
+use toml_edit::{Array, DocumentMut};
+
+use crate::components::base::utils::Position;
+use crate::components::daemon::client::reset_dbus_timeouts;
+
+pub const FRONTEND_CONFIG_FILE: &str = "frontend.toml";
+/// Volume that the daemon treats as 100%.
+pub const NORMAL_VOLUME: f64 = 65536.0;
+/// Matches the slider step of the templates.
+pub const DEFAULT_VOLUME_STEP: f64 = 3.06;
+/// Matches the slider range of the templates, roughly 153%.
+pub const DEFAULT_MAX_VOLUME: f64 = 153.0;
+
+// loaded on first use and replaced whenever the file changes
+static FRONTEND_CONFIG: RwLock<Option<FrontendConfig>> = RwLock::new(None);
+
+/// Settings of the graphical frontend, read from `~/.config/reset/frontend.toml`.
+/// Missing or invalid values fall back to the defaults.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrontendConfig {
+    pub start_page: Option<Position>,
+    pub hidden_entries: Vec<String>,
+    /// In percent.
+    pub volume_step: f64,
+    /// In percent.
+    pub max_volume: f64,
+    pub dbus_timeout: Option<Duration>,
+    pub dbus_long_timeout: Option<Duration>,
+    pub daemon_timeout: Option<Duration>,
+    /// Only these plugins are loaded if set.
+    pub allowed_plugins: Option<Vec<String>>,
+    pub denied_plugins: Vec<String>,
+    /// Reloads plugins whenever their library is rebuilt, meant for plugin development.
+    pub hot_reload_plugins: bool,
+    pub window_size: Option<(i32, i32)>,
+}
+
+impl Default for FrontendConfig {
+    fn default() -> Self {
+        Self {
+            start_page: None,
+            hidden_entries: Vec::new(),
+            volume_step: DEFAULT_VOLUME_STEP,
+            max_volume: DEFAULT_MAX_VOLUME,
+            dbus_timeout: None,
+            dbus_long_timeout: None,
+            daemon_timeout: None,
+            allowed_plugins: None,
+            denied_plugins: Vec::new(),
+            hot_reload_plugins: false,
+            window_size: None,
+        }
+    }
+}
+
+impl FrontendConfig {
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let table = content.parse::<Table>()?;
+        let default = Self::default();
+        let section = |name: &str| table.get(name).and_then(Value::as_table);
+        let audio = section("audio");
+        let dbus = section("dbus");
+        let plugins = section("plugins");
+        let window = section("window");
+
+        let width = window.and_then(|window| positive_integer(window, "width"));
+        let height = window.and_then(|window| positive_integer(window, "height"));
+        Ok(Self {
+            start_page: table
+                .get("start_page")
+                .and_then(Value::as_str)
+                .and_then(Position::from_page_name),
+            hidden_entries: table
+                .get("hidden_entries")
+                .and_then(string_list)
+                .unwrap_or_default(),
+            volume_step: audio
+                .and_then(|audio| percentage(audio, "volume_step"))
+                .unwrap_or(default.volume_step),
+            max_volume: audio
+                .and_then(|audio| percentage(audio, "max_volume"))
+                .unwrap_or(default.max_volume),
+            dbus_timeout: dbus.and_then(|dbus| milliseconds(dbus, "timeout")),
+            dbus_long_timeout: dbus.and_then(|dbus| milliseconds(dbus, "long_timeout")),
+            daemon_timeout: dbus.and_then(|dbus| milliseconds(dbus, "daemon_timeout")),
+            allowed_plugins: plugins
+                .and_then(|plugins| plugins.get("allow"))
+                .and_then(string_list),
+            denied_plugins: plugins
+                .and_then(|plugins| plugins.get("deny"))
+                .and_then(string_list)
+                .unwrap_or_default(),
+            hot_reload_plugins: plugins
+                .and_then(|plugins| plugins.get("hot_reload"))
+                .and_then(Value::as_bool)
+                .unwrap_or(default.hot_reload_plugins),
+            window_size: width.zip(height),
+        })
+    }
+
+    /// Hidden entries are matched case-insensitively against the sidebar names.
+    pub fn is_hidden(&self, sidebar_name: &str) -> bool {
+        self.hidden_entries
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(sidebar_name))
+    }
+
+    /// Plugins are referred to by their name or the file name of their library.
+    pub fn plugin_enabled(&self, identifiers: &[&str]) -> bool {
+        let matches = |entry: &String| {
+            identifiers
+                .iter()
+                .any(|identifier| entry.eq_ignore_ascii_case(identifier))
+        };
+        if self.denied_plugins.iter().any(matches) {
+            return false;
+        }
+        self.allowed_plugins
+            .as_ref()
+            .map_or(true, |allowed| allowed.iter().any(matches))
+    }
+
+    /// Allows skipping a plugin before asking it for its name.
+    pub fn plugin_denied(&self, identifier: &str) -> bool {
+        self.denied_plugins
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(identifier))
+    }
+
+    /// Volume step in the unit of the daemon.
+    pub fn volume_step_value(&self) -> f64 {
+        self.volume_step * NORMAL_VOLUME / 100.0
+    }
+
+    /// Maximum volume in the unit of the daemon.
+    pub fn max_volume_value(&self) -> f64 {
+        self.max_volume * NORMAL_VOLUME / 100.0
+    }
+}
+
+fn string_list(value: &Value) -> Option<Vec<String>> {
+    let list = value.as_array()?;
+    Some(
+        list.iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+    )
+}
+
+fn percentage(table: &Table, entry: &str) -> Option<f64> {
+    let value = table.get(entry)?;
+    let value = value
+        .as_float()
+        .or_else(|| value.as_integer().map(|value| value as f64))?;
+    (value > 0.0).then_some(value)
+}
+
+fn milliseconds(table: &Table, entry: &str) -> Option<Duration> {
+    let millis = table.get(entry)?.as_integer()?;
+    Some(Duration::from_millis(millis.max(0) as u64))
+}
+
+fn positive_integer(table: &Table, entry: &str) -> Option<i32> {
+    let value = table.get(entry)?.as_integer()?;
+    i32::try_from(value).ok().filter(|value| *value > 0)
+}
+
+pub fn frontend_config_path() -> Option<PathBuf> {
+    create_config_directory("reset").map(|directory| directory.join(FRONTEND_CONFIG_FILE))
+}
+
+/// A missing file results in the default config, a broken one in an error.
+fn read_frontend_config() -> Option<FrontendConfig> {
+    let path = frontend_config_path()?;
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Some(FrontendConfig::default()),
+    };
+    match FrontendConfig::parse(&content) {
+        Ok(config) => Some(config),
+        Err(_error) => {
+            ERROR!(
+                format!("Could not parse the frontend config: {}", _error),
+                ErrorLevel::Recoverable
+            );
+            None
+        }
+    }
+}
+
+pub fn frontend_config() -> FrontendConfig {
+    if let Some(config) = FRONTEND_CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return config.clone();
+    }
+    let config = read_frontend_config().unwrap_or_default();
+    let mut stored = FRONTEND_CONFIG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    stored.get_or_insert(config).clone()
+}
+
+/// Rereads the config file, returning the new config if it changed.
+/// A config with errors is ignored, as it is likely still being edited.
+pub fn reload_frontend_config() -> Option<FrontendConfig> {
+    let config = read_frontend_config()?;
+    let mut stored = FRONTEND_CONFIG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if stored.as_ref() == Some(&config) {
+        return None;
+    }
+    stored.replace(config.clone());
+    reset_dbus_timeouts();
+    Some(config)
+}
+
+/// Enables or disables a plugin by editing the `[plugins]` section of the config file,
+/// the rest of the file, including comments, is kept as is.
+/// `identifiers` are the file name of the library followed by the name of the plugin if known.
+pub fn set_plugin_enabled(identifiers: &[&str], enabled: bool) -> Result<(), String> {
+    let path = frontend_config_path().ok_or("Could not find the config directory")?;
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|error| format!("Could not parse the frontend config: {}", error))?;
+    let plugins = document
+        .entry("plugins")
+        .or_insert(toml_edit::table())
+        .as_table_mut()
+        .ok_or("plugins in the frontend config is not a table")?;
+    let matches = |entry: &toml_edit::Value| {
+        entry.as_str().is_some_and(|entry| {
+            identifiers
+                .iter()
+                .any(|identifier| entry.eq_ignore_ascii_case(identifier))
+        })
+    };
+
+    let deny = plugins
+        .entry("deny")
+        .or_insert(toml_edit::value(Array::new()))
+        .as_array_mut()
+        .ok_or("deny in the frontend config is not an array")?;
+    deny.retain(|entry| !matches(entry));
+    if !enabled {
+        deny.push(identifiers[0]);
+    }
+    if enabled {
+        if let Some(allow) = plugins
+            .get_mut("allow")
+            .and_then(|allow| allow.as_array_mut())
+        {
+            if !allow.iter().any(matches) {
+                allow.push(identifiers[0]);
+            }
+        }
+    }
+    fs::write(path, document.to_string())
+        .map_err(|error| format!("Could not write the frontend config: {}", error))
+}
+
+/// Calls `on_change` with the old and the new config whenever the config file is edited.
+/// The returned monitor has to be kept alive for as long as changes should be reported.
+pub fn watch_frontend_config<F: Fn(&FrontendConfig, &FrontendConfig) + 'static>(
+    on_change: F,
+) -> Option<gio::FileMonitor> {
+    let path = frontend_config_path()?;
+    let monitor =
+        gio::File::for_path(path).monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE);
+    let monitor = match monitor {
+        Ok(monitor) => monitor,
+        Err(_error) => {
+            ERROR!(
+                format!("Could not watch the frontend config: {}", _error),
+                ErrorLevel::Recoverable
+            );
+            return None;
+        }
+    };
+    monitor.connect_changed(move |_, _, _, event| {
+        if !matches!(
+            event,
+            gio::FileMonitorEvent::ChangesDoneHint
+                | gio::FileMonitorEvent::Created
+                | gio::FileMonitorEvent::Deleted
+        ) {
+            return;
+        }
+        let old_config = frontend_config();
+        if let Some(new_config) = reload_frontend_config() {
+            on_change(&old_config, &new_config);
+        }
+    });
+    Some(monitor)
+}
@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use re_set_lib::{create_config_directory, ERROR};
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+use toml::{Table, Value};
+
+use crate::components::base::utils::Position;
+
+pub const WINDOW_STATE_FILE: &str = "window_state.toml";
+
+// recorded when the window closes, written to disk on shutdown
+static WINDOW_STATE: Mutex<Option<WindowState>> = Mutex::new(None);
+
+/// State of the window that is restored on the next launch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindowState {
+    pub size: Option<(i32, i32)>,
+    pub sidebar_collapsed: bool,
+    /// Last visited page, `None` if no page was open.
+    pub position: Option<Position>,
+}
+
+impl WindowState {
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let table = content.parse::<Table>()?;
+        let dimension = |entry: &str| {
+            table
+                .get(entry)
+                .and_then(Value::as_integer)
+                .and_then(|value| i32::try_from(value).ok())
+                .filter(|value| *value > 0)
+        };
+        Ok(Self {
+            size: dimension("width").zip(dimension("height")),
+            sidebar_collapsed: table
+                .get("sidebar_collapsed")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            position: table
+                .get("page")
+                .and_then(Value::as_str)
+                .and_then(Position::from_page_name),
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = Table::new();
+        if let Some((width, height)) = self.size {
+            table.insert(String::from("width"), Value::Integer(width as i64));
+            table.insert(String::from("height"), Value::Integer(height as i64));
+        }
+        table.insert(
+            String::from("sidebar_collapsed"),
+            Value::Boolean(self.sidebar_collapsed),
+        );
+        if let Some(page) = self.position.as_ref().map(Position::page_name) {
+            table.insert(String::from("page"), Value::String(page));
+        }
+        table.to_string()
+    }
+}
+
+pub fn window_state_path() -> Option<PathBuf> {
+    create_config_directory("reset").map(|directory| directory.join(WINDOW_STATE_FILE))
+}
+
+/// A missing or broken state file results in the default state.
+pub fn load_window_state() -> WindowState {
+    let content = match window_state_path().map(fs::read_to_string) {
+        Some(Ok(content)) => content,
+        _ => return WindowState::default(),
+    };
+    WindowState::parse(&content).unwrap_or_else(|_error| {
+        ERROR!(
+            format!("Could not parse the window state: {}", _error),
+            ErrorLevel::Recoverable
+        );
+        WindowState::default()
+    })
+}
+
+/// Remembers the state of a closing window until it is written by [`save_window_state`].
+pub fn record_window_state(state: WindowState) {
+    WINDOW_STATE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(state);
+}
+
+pub fn save_window_state() {
+    let state = WINDOW_STATE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let (state, path) = match (state, window_state_path()) {
+        (Some(state), Some(path)) => (state, path),
+        _ => return,
+    };
+    if let Err(_error) = fs::write(path, state.to_toml()) {
+        ERROR!(
+            format!("Could not save the window state: {}", _error),
+            ErrorLevel::Recoverable
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use dbus::blocking::SyncConnection;
+use dbus::Error;
+use re_set_lib::utils::config::get_config_value;
+
+use crate::components::config::frontend::frontend_config;
+
+/// Used when no D-Bus timeout is configured.
+pub const DEFAULT_DBUS_TIMEOUT: Duration = Duration::from_millis(1000);
+/// Used when no long D-Bus timeout is configured, covers slow operations like connecting to networks.
+pub const DEFAULT_LONG_DBUS_TIMEOUT: Duration = Duration::from_millis(10000);
+
+// Method calls share a single connection, listeners still use their own as they need to
+// process incoming signals.
+static CONNECTION: Mutex<Option<Arc<SyncConnection>>> = Mutex::new(None);
+
+/// Reads a duration in milliseconds from the `[Frontend]` section of the ReSet config,
+/// which predates `frontend.toml` and is only used when the latter does not set a value.
+pub fn config_duration(entry: &'static str, default: Duration) -> Duration {
+    let duration = Cell::new(default);
+    get_config_value("Frontend", entry, |value| {
+        if let Some(millis) = value.as_integer() {
+            duration.set(Duration::from_millis(millis.max(0) as u64));
+        }
+    });
+    duration.get()
+}
+
+/// Marks a cached timeout as not yet resolved.
+const UNRESOLVED: u64 = u64::MAX;
+// resolved on first use, every D-Bus call needs them and the config is comparatively expensive to read
+static DBUS_TIMEOUT: AtomicU64 = AtomicU64::new(UNRESOLVED);
+static LONG_DBUS_TIMEOUT: AtomicU64 = AtomicU64::new(UNRESOLVED);
+
+fn cached_duration(cache: &AtomicU64, resolve: impl FnOnce() -> Duration) -> Duration {
+    let millis = cache.load(Ordering::SeqCst);
+    if millis != UNRESOLVED {
+        return Duration::from_millis(millis);
+    }
+    let duration = resolve();
+    let millis = u64::try_from(duration.as_millis()).unwrap_or(UNRESOLVED - 1);
+    cache.store(millis.min(UNRESOLVED - 1), Ordering::SeqCst);
+    duration
+}
+
+pub fn dbus_timeout() -> Duration {
+    cached_duration(&DBUS_TIMEOUT, || {
+        frontend_config()
+            .dbus_timeout
+            .unwrap_or_else(|| config_duration("dbus_timeout", DEFAULT_DBUS_TIMEOUT))
+    })
+}
+
+pub fn long_dbus_timeout() -> Duration {
+    cached_duration(&LONG_DBUS_TIMEOUT, || {
+        frontend_config()
+            .dbus_long_timeout
+            .unwrap_or_else(|| config_duration("dbus_long_timeout", DEFAULT_LONG_DBUS_TIMEOUT))
+    })
+}
+
+/// Resolves the timeouts again on their next use, called whenever the frontend config changed.
+pub fn reset_dbus_timeouts() {
+    DBUS_TIMEOUT.store(UNRESOLVED, Ordering::SeqCst);
+    LONG_DBUS_TIMEOUT.store(UNRESOLVED, Ordering::SeqCst);
+}
+
+/// Returns the shared session bus connection, connecting if there is none yet.
+pub fn connection() -> Result<Arc<SyncConnection>, Error> {
+    let mut connection = CONNECTION.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(conn) = connection.as_ref() {
+        if conn.channel().is_connected() {
+            return Ok(conn.clone());
+        }
+    }
+    let conn = Arc::new(SyncConnection::new_session()?);
+    *connection = Some(conn.clone());
+    Ok(conn)
+}
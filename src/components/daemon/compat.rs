@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+
+use crate::components::daemon::proxy::{
+    base::BaseProxy, method_signatures, parse_member_signatures, signal_signatures, DBusMethod,
+    DaemonCall, DaemonClient,
+};
+
+/// Daemon version this frontend was built against.
+pub const EXPECTED_DAEMON_VERSION: &str = reset_daemon::VERSION;
+pub const INTROSPECT: DBusMethod<(), (String,)> =
+    DBusMethod::new("org.freedesktop.DBus.Introspectable", "Introspect");
+
+/// A method the frontend calls on the daemon, as interface and method name.
+pub type DaemonMethod = (&'static str, &'static str);
+
+/// Version and method information of the running daemon, queried after registering.
+/// An unknown method list means that nothing gets disabled.
+#[derive(Default, Clone, Debug)]
+pub struct DaemonCompatibility {
+    pub version: Option<String>,
+    pub methods: Option<HashMap<String, HashSet<String>>>,
+}
+
+impl DaemonCompatibility {
+    pub fn query() -> Self {
+        let client = DaemonClient::new();
+        let version = client.api_version();
+        if version.is_err() {
+            ERROR!(
+                "Could not query the daemon version",
+                ErrorLevel::Recoverable
+            );
+        }
+        let introspection = client.call(&INTROSPECT, ());
+        match &introspection {
+            Ok((introspection,)) => log_signature_mismatches(introspection),
+            Err(_) => ERROR!(
+                "Could not introspect the daemon interfaces",
+                ErrorLevel::Recoverable
+            ),
+        }
+
+        Self {
+            version: version.ok(),
+            methods: introspection
+                .ok()
+                .map(|introspection| parse_introspection(&introspection.0)),
+        }
+    }
+
+    pub fn supports(&self, required_methods: &[DaemonMethod]) -> bool {
+        let methods = match &self.methods {
+            Some(methods) => methods,
+            None => return true,
+        };
+        required_methods.iter().all(|(interface, method)| {
+            methods.get(*interface).map_or(false, |interface_methods| {
+                interface_methods.contains(*method)
+            })
+        })
+    }
+
+    /// Message for the banner, None if the daemon is compatible.
+    pub fn mismatch_message(&self) -> Option<String> {
+        match &self.version {
+            Some(version) if versions_compatible(EXPECTED_DAEMON_VERSION, version) => None,
+            Some(version) => Some(format!(
+                "Daemon version {} does not match the expected version {}, some pages may be unavailable",
+                version, EXPECTED_DAEMON_VERSION
+            )),
+            None => Some(String::from(
+                "The daemon did not report its version, some pages may be unavailable",
+            )),
+        }
+    }
+}
+
+/// Daemons of the same major version are compatible as long as they are not older
+/// than the expected minor version, newer daemons only add methods.
+pub fn versions_compatible(expected: &str, actual: &str) -> bool {
+    let parse = |version: &str| -> Option<(u64, u64)> {
+        let mut parts = version.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some((major, minor))
+    };
+    match (parse(expected), parse(actual)) {
+        (Some((expected_major, expected_minor)), Some((actual_major, actual_minor))) => {
+            expected_major == actual_major && actual_minor >= expected_minor
+        }
+        _ => false,
+    }
+}
+
+/// Calls to methods whose signature changed in the daemon fail to decode, log them up front.
+fn log_signature_mismatches(introspection: &str) {
+    let daemon_members = parse_member_signatures(introspection);
+    for member in method_signatures().into_iter().chain(signal_signatures()) {
+        let declared = daemon_members.iter().find(|declared| {
+            declared.interface == member.interface && declared.name == member.name
+        });
+        if declared.map_or(false, |declared| *declared != member) {
+            ERROR!(
+                format!(
+                    "Signature of {}.{} does not match the daemon",
+                    member.interface, member.name
+                ),
+                ErrorLevel::Recoverable
+            );
+        }
+    }
+}
+
+/// Collects the methods of each interface from D-Bus introspection xml.
+pub fn parse_introspection(xml: &str) -> HashMap<String, HashSet<String>> {
+    let mut interfaces: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut current_interface: Option<String> = None;
+    for tag in xml.split('<').map(str::trim) {
+        if tag.starts_with("interface ") {
+            current_interface = attribute_value(tag, "name");
+            if let Some(interface) = &current_interface {
+                interfaces.entry(interface.clone()).or_default();
+            }
+        } else if tag.starts_with("/interface") {
+            current_interface = None;
+        } else if tag.starts_with("method ") {
+            if let (Some(interface), Some(method)) =
+                (&current_interface, attribute_value(tag, "name"))
+            {
+                interfaces
+                    .entry(interface.clone())
+                    .or_default()
+                    .insert(method);
+            }
+        }
+    }
+    interfaces
+}
+
+pub fn attribute_value(tag: &str, attribute: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", attribute))? + attribute.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
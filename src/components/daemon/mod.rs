@@ -0,0 +1,5 @@
+pub mod client;
+pub mod compat;
+pub mod proxy;
+pub mod readiness;
+pub mod reconnect;
@@ -0,0 +1,105 @@
+use dbus::Error;
+use re_set_lib::audio::audio_structures::{Card, InputStream, OutputStream, Sink, Source};
+use re_set_lib::signals::{
+    InputStreamAdded, InputStreamChanged, InputStreamRemoved, OutputStreamAdded,
+    OutputStreamChanged, OutputStreamRemoved, SinkAdded, SinkChanged, SinkRemoved, SourceAdded,
+    SourceChanged, SourceRemoved,
+};
+
+use crate::components::utils::AUDIO;
+
+use super::{signal_signature, DBusMethod, DaemonCall, MemberSignature};
+
+/// Index, channels and volume of a sink, source or stream.
+pub type VolumeArgs = (u32, u16, u32);
+/// Index and mute state of a sink, source or stream.
+pub type MuteArgs = (u32, bool);
+
+pub const GET_DEFAULT_SINK: DBusMethod<(), (Sink,)> = DBusMethod::new(AUDIO, "GetDefaultSink");
+pub const GET_DEFAULT_SINK_NAME: DBusMethod<(), (String,)> =
+    DBusMethod::new(AUDIO, "GetDefaultSinkName");
+pub const GET_DEFAULT_SOURCE: DBusMethod<(), (Source,)> =
+    DBusMethod::new(AUDIO, "GetDefaultSource");
+pub const GET_DEFAULT_SOURCE_NAME: DBusMethod<(), (String,)> =
+    DBusMethod::new(AUDIO, "GetDefaultSourceName");
+pub const LIST_SINKS: DBusMethod<(), (Vec<Sink>,)> = DBusMethod::new(AUDIO, "ListSinks");
+pub const LIST_SOURCES: DBusMethod<(), (Vec<Source>,)> = DBusMethod::new(AUDIO, "ListSources");
+pub const SET_SINK_VOLUME: DBusMethod<VolumeArgs, ()> = DBusMethod::new(AUDIO, "SetSinkVolume");
+pub const SET_SINK_MUTE: DBusMethod<MuteArgs, ()> = DBusMethod::new(AUDIO, "SetSinkMute");
+pub const SET_SOURCE_VOLUME: DBusMethod<VolumeArgs, ()> = DBusMethod::new(AUDIO, "SetSourceVolume");
+pub const SET_SOURCE_MUTE: DBusMethod<MuteArgs, ()> = DBusMethod::new(AUDIO, "SetSourceMute");
+pub const SET_DEFAULT_SINK: DBusMethod<(String,), (Sink,)> =
+    DBusMethod::new(AUDIO, "SetDefaultSink");
+pub const SET_DEFAULT_SOURCE: DBusMethod<(String,), (Source,)> =
+    DBusMethod::new(AUDIO, "SetDefaultSource");
+pub const LIST_INPUT_STREAMS: DBusMethod<(), (Vec<InputStream>,)> =
+    DBusMethod::new(AUDIO, "ListInputStreams");
+pub const SET_SINK_OF_INPUT_STREAM: DBusMethod<(u32, u32), ()> =
+    DBusMethod::new(AUDIO, "SetSinkOfInputStream");
+pub const SET_INPUT_STREAM_VOLUME: DBusMethod<VolumeArgs, ()> =
+    DBusMethod::new(AUDIO, "SetInputStreamVolume");
+pub const SET_INPUT_STREAM_MUTE: DBusMethod<MuteArgs, ()> =
+    DBusMethod::new(AUDIO, "SetInputStreamMute");
+pub const LIST_OUTPUT_STREAMS: DBusMethod<(), (Vec<OutputStream>,)> =
+    DBusMethod::new(AUDIO, "ListOutputStreams");
+pub const SET_SOURCE_OF_OUTPUT_STREAM: DBusMethod<(u32, u32), ()> =
+    DBusMethod::new(AUDIO, "SetSourceOfOutputStream");
+pub const SET_OUTPUT_STREAM_VOLUME: DBusMethod<VolumeArgs, ()> =
+    DBusMethod::new(AUDIO, "SetOutputStreamVolume");
+pub const SET_OUTPUT_STREAM_MUTE: DBusMethod<MuteArgs, ()> =
+    DBusMethod::new(AUDIO, "SetOutputStreamMute");
+pub const LIST_CARDS: DBusMethod<(), (Vec<Card>,)> = DBusMethod::new(AUDIO, "ListCards");
+pub const SET_CARD_PROFILE_OF_DEVICE: DBusMethod<(u32, String), ()> =
+    DBusMethod::new(AUDIO, "SetCardProfileOfDevice");
+
+/// Sink, source and stream methods are called generically through
+/// [`DBusFunction`](crate::components::audio::audio_entry::DBusFunction), only cards go through here.
+pub trait AudioProxy: DaemonCall {
+    fn set_card_profile_of_device(&self, index: u32, profile: String) -> Result<(), Error> {
+        self.call(&SET_CARD_PROFILE_OF_DEVICE, (index, profile))
+    }
+}
+
+pub fn method_signatures() -> Vec<MemberSignature> {
+    vec![
+        GET_DEFAULT_SINK.signature(),
+        GET_DEFAULT_SINK_NAME.signature(),
+        GET_DEFAULT_SOURCE.signature(),
+        GET_DEFAULT_SOURCE_NAME.signature(),
+        LIST_SINKS.signature(),
+        LIST_SOURCES.signature(),
+        SET_SINK_VOLUME.signature(),
+        SET_SINK_MUTE.signature(),
+        SET_SOURCE_VOLUME.signature(),
+        SET_SOURCE_MUTE.signature(),
+        SET_DEFAULT_SINK.signature(),
+        SET_DEFAULT_SOURCE.signature(),
+        LIST_INPUT_STREAMS.signature(),
+        SET_SINK_OF_INPUT_STREAM.signature(),
+        SET_INPUT_STREAM_VOLUME.signature(),
+        SET_INPUT_STREAM_MUTE.signature(),
+        LIST_OUTPUT_STREAMS.signature(),
+        SET_SOURCE_OF_OUTPUT_STREAM.signature(),
+        SET_OUTPUT_STREAM_VOLUME.signature(),
+        SET_OUTPUT_STREAM_MUTE.signature(),
+        LIST_CARDS.signature(),
+        SET_CARD_PROFILE_OF_DEVICE.signature(),
+    ]
+}
+
+pub fn signal_signatures() -> Vec<MemberSignature> {
+    vec![
+        signal_signature::<SinkAdded, (Sink,)>(),
+        signal_signature::<SinkChanged, (Sink,)>(),
+        signal_signature::<SinkRemoved, (u32,)>(),
+        signal_signature::<SourceAdded, (Source,)>(),
+        signal_signature::<SourceChanged, (Source,)>(),
+        signal_signature::<SourceRemoved, (u32,)>(),
+        signal_signature::<InputStreamAdded, (InputStream,)>(),
+        signal_signature::<InputStreamChanged, (InputStream,)>(),
+        signal_signature::<InputStreamRemoved, (u32,)>(),
+        signal_signature::<OutputStreamAdded, (OutputStream,)>(),
+        signal_signature::<OutputStreamChanged, (OutputStream,)>(),
+        signal_signature::<OutputStreamRemoved, (u32,)>(),
+    ]
+}
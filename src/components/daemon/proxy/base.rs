@@ -0,0 +1,43 @@
+use dbus::Error;
+
+use crate::components::utils::BASE;
+
+use super::{DBusMethod, DaemonCall, MemberSignature};
+
+pub const API_VERSION: DBusMethod<(), (String,)> = DBusMethod::new(BASE, "APIVersion");
+pub const GET_CAPABILITIES: DBusMethod<(), (Vec<String>,)> =
+    DBusMethod::new(BASE, "GetCapabilities");
+pub const REGISTER_CLIENT: DBusMethod<(String,), (bool,)> =
+    DBusMethod::with_status(BASE, "RegisterClient");
+pub const UNREGISTER_CLIENT: DBusMethod<(String,), (bool,)> =
+    DBusMethod::with_status(BASE, "UnregisterClient");
+
+pub trait BaseProxy: DaemonCall {
+    fn api_version(&self) -> Result<String, Error> {
+        self.call(&API_VERSION, ()).map(|(version,)| version)
+    }
+
+    fn get_capabilities(&self) -> Result<Vec<String>, Error> {
+        self.call(&GET_CAPABILITIES, ())
+            .map(|(capabilities,)| capabilities)
+    }
+
+    fn register_client(&self, name: &str) -> Result<bool, Error> {
+        self.call(&REGISTER_CLIENT, (name.to_string(),))
+            .map(|(result,)| result)
+    }
+
+    fn unregister_client(&self, name: &str) -> Result<bool, Error> {
+        self.call(&UNREGISTER_CLIENT, (name.to_string(),))
+            .map(|(result,)| result)
+    }
+}
+
+pub fn method_signatures() -> Vec<MemberSignature> {
+    vec![
+        API_VERSION.signature(),
+        GET_CAPABILITIES.signature(),
+        REGISTER_CLIENT.signature(),
+        UNREGISTER_CLIENT.signature(),
+    ]
+}
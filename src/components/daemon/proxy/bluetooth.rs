@@ -0,0 +1,127 @@
+use dbus::{Error, Path};
+use re_set_lib::bluetooth::bluetooth_structures::{BluetoothAdapter, BluetoothDevice};
+use re_set_lib::signals::{BluetoothDeviceAdded, BluetoothDeviceChanged, BluetoothDeviceRemoved};
+
+use crate::components::utils::BLUETOOTH;
+
+use super::{signal_signature, DBusMethod, DaemonCall, MemberSignature};
+
+pub const START_BLUETOOTH_LISTENER: DBusMethod<(), ()> =
+    DBusMethod::new(BLUETOOTH, "StartBluetoothListener");
+pub const STOP_BLUETOOTH_LISTENER: DBusMethod<(), ()> =
+    DBusMethod::new(BLUETOOTH, "StopBluetoothListener");
+pub const STOP_BLUETOOTH_SCAN: DBusMethod<(), ()> = DBusMethod::new(BLUETOOTH, "StopBluetoothScan");
+pub const GET_BLUETOOTH_ADAPTERS: DBusMethod<(), (Vec<BluetoothAdapter>,)> =
+    DBusMethod::new(BLUETOOTH, "GetBluetoothAdapters");
+pub const SET_BLUETOOTH_ADAPTER: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "SetBluetoothAdapter");
+pub const SET_BLUETOOTH_ADAPTER_ENABLED: DBusMethod<(Path<'static>, bool), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "SetBluetoothAdapterEnabled");
+pub const SET_BLUETOOTH_ADAPTER_DISCOVERABILITY: DBusMethod<(Path<'static>, bool), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "SetBluetoothAdapterDiscoverability");
+pub const SET_BLUETOOTH_ADAPTER_PAIRABILITY: DBusMethod<(Path<'static>, bool), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "SetBluetoothAdapterPairability");
+pub const GET_BLUETOOTH_DEVICES: DBusMethod<(), (Vec<BluetoothDevice>,)> =
+    DBusMethod::new(BLUETOOTH, "GetBluetoothDevices");
+pub const CONNECT_TO_BLUETOOTH_DEVICE: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "ConnectToBluetoothDevice");
+pub const DISCONNECT_FROM_BLUETOOTH_DEVICE: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "DisconnectFromBluetoothDevice");
+pub const REMOVE_DEVICE_PAIRING: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(BLUETOOTH, "RemoveDevicePairing");
+
+pub trait BluetoothProxy: DaemonCall {
+    fn start_bluetooth_listener(&self) -> Result<(), Error> {
+        self.call(&START_BLUETOOTH_LISTENER, ())
+    }
+
+    fn stop_bluetooth_listener(&self) -> Result<(), Error> {
+        self.call(&STOP_BLUETOOTH_LISTENER, ())
+    }
+
+    fn stop_bluetooth_scan(&self) -> Result<(), Error> {
+        self.call(&STOP_BLUETOOTH_SCAN, ())
+    }
+
+    fn get_bluetooth_adapters(&self) -> Result<Vec<BluetoothAdapter>, Error> {
+        self.call(&GET_BLUETOOTH_ADAPTERS, ())
+            .map(|(adapters,)| adapters)
+    }
+
+    fn set_bluetooth_adapter(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&SET_BLUETOOTH_ADAPTER, (path,))
+            .map(|(result,)| result)
+    }
+
+    fn set_bluetooth_adapter_enabled(
+        &self,
+        path: Path<'static>,
+        enabled: bool,
+    ) -> Result<bool, Error> {
+        self.call(&SET_BLUETOOTH_ADAPTER_ENABLED, (path, enabled))
+            .map(|(result,)| result)
+    }
+
+    fn set_bluetooth_adapter_discoverability(
+        &self,
+        path: Path<'static>,
+        enabled: bool,
+    ) -> Result<bool, Error> {
+        self.call(&SET_BLUETOOTH_ADAPTER_DISCOVERABILITY, (path, enabled))
+            .map(|(result,)| result)
+    }
+
+    fn set_bluetooth_adapter_pairability(
+        &self,
+        path: Path<'static>,
+        enabled: bool,
+    ) -> Result<bool, Error> {
+        self.call(&SET_BLUETOOTH_ADAPTER_PAIRABILITY, (path, enabled))
+            .map(|(result,)| result)
+    }
+
+    fn get_bluetooth_devices(&self) -> Result<Vec<BluetoothDevice>, Error> {
+        self.call(&GET_BLUETOOTH_DEVICES, ())
+            .map(|(devices,)| devices)
+    }
+
+    fn connect_to_bluetooth_device(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&CONNECT_TO_BLUETOOTH_DEVICE, (path,))
+            .map(|(result,)| result)
+    }
+
+    fn disconnect_from_bluetooth_device(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&DISCONNECT_FROM_BLUETOOTH_DEVICE, (path,))
+            .map(|(result,)| result)
+    }
+
+    fn remove_device_pairing(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&REMOVE_DEVICE_PAIRING, (path,))
+            .map(|(result,)| result)
+    }
+}
+
+pub fn method_signatures() -> Vec<MemberSignature> {
+    vec![
+        START_BLUETOOTH_LISTENER.signature(),
+        STOP_BLUETOOTH_LISTENER.signature(),
+        STOP_BLUETOOTH_SCAN.signature(),
+        GET_BLUETOOTH_ADAPTERS.signature(),
+        SET_BLUETOOTH_ADAPTER.signature(),
+        SET_BLUETOOTH_ADAPTER_ENABLED.signature(),
+        SET_BLUETOOTH_ADAPTER_DISCOVERABILITY.signature(),
+        SET_BLUETOOTH_ADAPTER_PAIRABILITY.signature(),
+        GET_BLUETOOTH_DEVICES.signature(),
+        CONNECT_TO_BLUETOOTH_DEVICE.signature(),
+        DISCONNECT_FROM_BLUETOOTH_DEVICE.signature(),
+        REMOVE_DEVICE_PAIRING.signature(),
+    ]
+}
+
+pub fn signal_signatures() -> Vec<MemberSignature> {
+    vec![
+        signal_signature::<BluetoothDeviceAdded, (BluetoothDevice,)>(),
+        signal_signature::<BluetoothDeviceChanged, (BluetoothDevice,)>(),
+        signal_signature::<BluetoothDeviceRemoved, (Path<'static>,)>(),
+    ]
+}
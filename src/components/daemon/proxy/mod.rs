@@ -0,0 +1,272 @@
+pub mod audio;
+pub mod base;
+pub mod bluetooth;
+pub mod network;
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use dbus::arg::{AppendAll, Arg, IterAppend, ReadAll};
+use dbus::blocking::BlockingSender;
+use dbus::message::SignalArgs;
+use dbus::{Error, Message};
+
+use crate::components::base::event_log::record_daemon_error;
+use crate::components::daemon::client::{connection, dbus_timeout, long_dbus_timeout};
+use crate::components::daemon::compat::{attribute_value, DaemonMethod};
+use crate::components::utils::{BASE, DBUS_PATH};
+
+/// A daemon method together with its argument and return types.
+/// Names and signatures of daemon calls are only declared through these.
+pub struct DBusMethod<I, O> {
+    pub interface: &'static str,
+    pub name: &'static str,
+    /// The method only returns whether it succeeded, a `false` result is logged like an error.
+    pub returns_status: bool,
+    types: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O> DBusMethod<I, O> {
+    pub const fn new(interface: &'static str, name: &'static str) -> Self {
+        Self {
+            interface,
+            name,
+            returns_status: false,
+            types: PhantomData,
+        }
+    }
+
+    pub const fn member(&self) -> DaemonMethod {
+        (self.interface, self.name)
+    }
+
+    /// Interface and name, e.g. `org.Xetibo.ReSet.Audio.SetSinkVolume`.
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.interface, self.name)
+    }
+}
+
+impl<I> DBusMethod<I, (bool,)> {
+    /// For methods whose result only tells whether the daemon succeeded,
+    /// unlike e.g. `GetWifiStatus`.
+    pub const fn with_status(interface: &'static str, name: &'static str) -> Self {
+        Self {
+            returns_status: true,
+            ..Self::new(interface, name)
+        }
+    }
+}
+
+impl<I: AppendAll, O: ReadAll> DBusMethod<I, O> {
+    /// Calls the method over the given connection, used by listeners and the command line
+    /// which keep their own connection.
+    pub fn call_on<C: BlockingSender>(
+        &self,
+        conn: &C,
+        args: I,
+        timeout: Duration,
+    ) -> Result<O, Error> {
+        self.reply_on(conn, args, timeout)
+            .and_then(|reply| reply.read_all())
+    }
+
+    fn reply_on<C: BlockingSender>(
+        &self,
+        conn: &C,
+        args: I,
+        timeout: Duration,
+    ) -> Result<Message, Error> {
+        let mut message = Message::method_call(
+            &BASE.into(),
+            &DBUS_PATH.into(),
+            &self.interface.into(),
+            &self.name.into(),
+        );
+        args.append(&mut IterAppend::new(&mut message));
+        conn.send_with_reply_and_block(message, timeout)
+    }
+
+    /// Whether the reply of a method with status reports a failure.
+    pub fn reports_failure(&self, reply: &Message) -> bool {
+        self.returns_status && reply.get1::<bool>() == Some(false)
+    }
+}
+
+/// Implemented by everything that can call the daemon, the proxy traits build on this.
+pub trait DaemonCall {
+    fn call<I: AppendAll, O: ReadAll>(
+        &self,
+        method: &DBusMethod<I, O>,
+        args: I,
+    ) -> Result<O, Error>;
+}
+
+/// Calls the daemon over the shared connection.
+pub struct DaemonClient {
+    timeout: Duration,
+}
+
+impl DaemonClient {
+    pub fn new() -> Self {
+        Self::with_timeout(dbus_timeout())
+    }
+
+    /// For slow operations like connecting to networks or devices.
+    pub fn long() -> Self {
+        Self::with_timeout(long_dbus_timeout())
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl Default for DaemonClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DaemonCall for DaemonClient {
+    fn call<I: AppendAll, O: ReadAll>(
+        &self,
+        method: &DBusMethod<I, O>,
+        args: I,
+    ) -> Result<O, Error> {
+        // every failed call is logged here, the callers only have to handle the result
+        let result = connection()
+            .and_then(|conn| method.reply_on(&*conn, args, self.timeout))
+            .and_then(|reply| {
+                let failed = method.reports_failure(&reply);
+                reply.read_all().map(|output| (output, failed))
+            });
+        match &result {
+            Ok((_, false)) => (),
+            Ok((_, true)) => record_daemon_error(
+                &method.qualified_name(),
+                "The daemon could not complete the call",
+            ),
+            Err(error) => record_daemon_error(
+                &method.qualified_name(),
+                error.message().unwrap_or("Unknown error"),
+            ),
+        }
+        result.map(|(output, _)| output)
+    }
+}
+
+impl base::BaseProxy for DaemonClient {}
+impl network::NetworkProxy for DaemonClient {}
+impl bluetooth::BluetoothProxy for DaemonClient {}
+impl audio::AudioProxy for DaemonClient {}
+
+/// D-Bus signature of a tuple of arguments, e.g. `(u32, bool)` results in `ub`.
+pub trait TupleSignature {
+    fn signature() -> String;
+}
+
+macro_rules! tuple_signature {
+    ($($types:ident),*) => {
+        impl<$($types: Arg),*> TupleSignature for ($($types,)*) {
+            fn signature() -> String {
+                let signature = String::new();
+                $(let signature = signature + &$types::signature();)*
+                signature
+            }
+        }
+    };
+}
+
+tuple_signature!();
+tuple_signature!(A);
+tuple_signature!(A, B);
+tuple_signature!(A, B, C);
+
+/// Name and D-Bus signatures of a method or signal, compared against the daemon introspection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberSignature {
+    pub interface: String,
+    pub name: String,
+    pub input: String,
+    pub output: String,
+}
+
+impl<I: TupleSignature, O: TupleSignature> DBusMethod<I, O> {
+    pub fn signature(&self) -> MemberSignature {
+        MemberSignature {
+            interface: self.interface.to_string(),
+            name: self.name.to_string(),
+            input: I::signature(),
+            output: O::signature(),
+        }
+    }
+}
+
+/// Signals carry their arguments as output.
+pub fn signal_signature<S: SignalArgs, T: TupleSignature>() -> MemberSignature {
+    MemberSignature {
+        interface: S::INTERFACE.to_string(),
+        name: S::NAME.to_string(),
+        input: String::new(),
+        output: T::signature(),
+    }
+}
+
+/// Every method the frontend calls on the daemon.
+pub fn method_signatures() -> Vec<MemberSignature> {
+    let mut methods = base::method_signatures();
+    methods.append(&mut network::method_signatures());
+    methods.append(&mut bluetooth::method_signatures());
+    methods.append(&mut audio::method_signatures());
+    methods
+}
+
+/// Every daemon signal the frontend listens to.
+pub fn signal_signatures() -> Vec<MemberSignature> {
+    let mut signals = network::signal_signatures();
+    signals.append(&mut bluetooth::signal_signatures());
+    signals.append(&mut audio::signal_signatures());
+    signals
+}
+
+/// Collects the signatures of all methods and signals from D-Bus introspection xml.
+pub fn parse_member_signatures(xml: &str) -> Vec<MemberSignature> {
+    let mut members = Vec::new();
+    let mut interface: Option<String> = None;
+    let mut member: Option<(MemberSignature, bool)> = None;
+    for tag in xml.split('<').map(str::trim) {
+        if tag.starts_with("interface ") {
+            interface = attribute_value(tag, "name");
+        } else if tag.starts_with("/interface") {
+            interface = None;
+        } else if tag.starts_with("method ") || tag.starts_with("signal ") {
+            let is_signal = tag.starts_with("signal ");
+            member = interface.as_ref().map(|interface| {
+                let signature = MemberSignature {
+                    interface: interface.clone(),
+                    name: attribute_value(tag, "name").unwrap_or_default(),
+                    input: String::new(),
+                    output: String::new(),
+                };
+                (signature, is_signal)
+            });
+            if tag.ends_with("/>") {
+                members.extend(member.take().map(|(signature, _)| signature));
+            }
+        } else if tag.starts_with("/method") || tag.starts_with("/signal") {
+            members.extend(member.take().map(|(signature, _)| signature));
+        } else if tag.starts_with("arg ") {
+            if let Some((signature, is_signal)) = member.as_mut() {
+                let arg_type = attribute_value(tag, "type").unwrap_or_default();
+                // method arguments default to in, signal arguments can only be out
+                let direction = attribute_value(tag, "direction");
+                if !*is_signal && direction.as_deref() != Some("out") {
+                    signature.input.push_str(&arg_type);
+                } else {
+                    signature.output.push_str(&arg_type);
+                }
+            }
+        }
+    }
+    members
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use dbus::arg::PropMap;
+use dbus::{Error, Path};
+use re_set_lib::network::network_structures::{AccessPoint, WifiDevice};
+use re_set_lib::signals::{
+    AccessPointAdded, AccessPointChanged, AccessPointRemoved, WifiDeviceChanged, WifiDeviceReset,
+};
+
+use crate::components::utils::WIRELESS;
+
+use super::{signal_signature, DBusMethod, DaemonCall, MemberSignature};
+
+pub type ConnectionSettings = HashMap<String, PropMap>;
+pub type StoredConnection = (Path<'static>, Vec<u8>);
+
+pub const LIST_ACCESS_POINTS: DBusMethod<(), (Vec<AccessPoint>,)> =
+    DBusMethod::new(WIRELESS, "ListAccessPoints");
+pub const GET_WIFI_STATUS: DBusMethod<(), (bool,)> = DBusMethod::new(WIRELESS, "GetWifiStatus");
+pub const SET_WIFI_ENABLED: DBusMethod<(bool,), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "SetWifiEnabled");
+pub const GET_CURRENT_WIFI_DEVICE: DBusMethod<(), (WifiDevice,)> =
+    DBusMethod::new(WIRELESS, "GetCurrentWifiDevice");
+pub const GET_ALL_WIFI_DEVICES: DBusMethod<(), (Vec<WifiDevice>,)> =
+    DBusMethod::new(WIRELESS, "GetAllWifiDevices");
+pub const SET_WIFI_DEVICE: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "SetWifiDevice");
+pub const CONNECT_TO_KNOWN_ACCESS_POINT: DBusMethod<(AccessPoint,), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "ConnectToKnownAccessPoint");
+pub const CONNECT_TO_NEW_ACCESS_POINT: DBusMethod<(AccessPoint, String), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "ConnectToNewAccessPoint");
+pub const DISCONNECT_FROM_CURRENT_ACCESS_POINT: DBusMethod<(), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "DisconnectFromCurrentAccessPoint");
+pub const LIST_STORED_CONNECTIONS: DBusMethod<(), (Vec<StoredConnection>,)> =
+    DBusMethod::new(WIRELESS, "ListStoredConnections");
+pub const GET_CONNECTION_SETTINGS: DBusMethod<(Path<'static>,), (ConnectionSettings,)> =
+    DBusMethod::new(WIRELESS, "GetConnectionSettings");
+pub const SET_CONNECTION_SETTINGS: DBusMethod<(Path<'static>, ConnectionSettings), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "SetConnectionSettings");
+pub const DELETE_CONNECTION: DBusMethod<(Path<'static>,), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "DeleteConnection");
+// false if the listener already runs, e.g. for another window
+pub const START_NETWORK_LISTENER: DBusMethod<(), (bool,)> =
+    DBusMethod::new(WIRELESS, "StartNetworkListener");
+pub const STOP_NETWORK_LISTENER: DBusMethod<(), (bool,)> =
+    DBusMethod::with_status(WIRELESS, "StopNetworkListener");
+
+pub trait NetworkProxy: DaemonCall {
+    fn list_access_points(&self) -> Result<Vec<AccessPoint>, Error> {
+        self.call(&LIST_ACCESS_POINTS, ())
+            .map(|(access_points,)| access_points)
+    }
+
+    fn get_wifi_status(&self) -> Result<bool, Error> {
+        self.call(&GET_WIFI_STATUS, ()).map(|(status,)| status)
+    }
+
+    fn set_wifi_enabled(&self, enabled: bool) -> Result<bool, Error> {
+        self.call(&SET_WIFI_ENABLED, (enabled,))
+            .map(|(result,)| result)
+    }
+
+    fn get_all_wifi_devices(&self) -> Result<Vec<WifiDevice>, Error> {
+        self.call(&GET_ALL_WIFI_DEVICES, ())
+            .map(|(devices,)| devices)
+    }
+
+    fn set_wifi_device(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&SET_WIFI_DEVICE, (path,)).map(|(result,)| result)
+    }
+
+    fn connect_to_known_access_point(&self, access_point: AccessPoint) -> Result<bool, Error> {
+        self.call(&CONNECT_TO_KNOWN_ACCESS_POINT, (access_point,))
+            .map(|(result,)| result)
+    }
+
+    fn connect_to_new_access_point(
+        &self,
+        access_point: AccessPoint,
+        password: String,
+    ) -> Result<bool, Error> {
+        self.call(&CONNECT_TO_NEW_ACCESS_POINT, (access_point, password))
+            .map(|(result,)| result)
+    }
+
+    fn disconnect_from_current_access_point(&self) -> Result<bool, Error> {
+        self.call(&DISCONNECT_FROM_CURRENT_ACCESS_POINT, ())
+            .map(|(result,)| result)
+    }
+
+    fn list_stored_connections(&self) -> Result<Vec<StoredConnection>, Error> {
+        self.call(&LIST_STORED_CONNECTIONS, ())
+            .map(|(connections,)| connections)
+    }
+
+    fn get_connection_settings(&self, path: Path<'static>) -> Result<ConnectionSettings, Error> {
+        self.call(&GET_CONNECTION_SETTINGS, (path,))
+            .map(|(settings,)| settings)
+    }
+
+    fn set_connection_settings(
+        &self,
+        path: Path<'static>,
+        settings: ConnectionSettings,
+    ) -> Result<bool, Error> {
+        self.call(&SET_CONNECTION_SETTINGS, (path, settings))
+            .map(|(result,)| result)
+    }
+
+    fn delete_connection(&self, path: Path<'static>) -> Result<bool, Error> {
+        self.call(&DELETE_CONNECTION, (path,))
+            .map(|(result,)| result)
+    }
+
+    fn start_network_listener(&self) -> Result<bool, Error> {
+        self.call(&START_NETWORK_LISTENER, ())
+            .map(|(result,)| result)
+    }
+
+    fn stop_network_listener(&self) -> Result<bool, Error> {
+        self.call(&STOP_NETWORK_LISTENER, ())
+            .map(|(result,)| result)
+    }
+}
+
+pub fn method_signatures() -> Vec<MemberSignature> {
+    vec![
+        LIST_ACCESS_POINTS.signature(),
+        GET_WIFI_STATUS.signature(),
+        SET_WIFI_ENABLED.signature(),
+        GET_CURRENT_WIFI_DEVICE.signature(),
+        GET_ALL_WIFI_DEVICES.signature(),
+        SET_WIFI_DEVICE.signature(),
+        CONNECT_TO_KNOWN_ACCESS_POINT.signature(),
+        CONNECT_TO_NEW_ACCESS_POINT.signature(),
+        DISCONNECT_FROM_CURRENT_ACCESS_POINT.signature(),
+        LIST_STORED_CONNECTIONS.signature(),
+        GET_CONNECTION_SETTINGS.signature(),
+        SET_CONNECTION_SETTINGS.signature(),
+        DELETE_CONNECTION.signature(),
+        START_NETWORK_LISTENER.signature(),
+        STOP_NETWORK_LISTENER.signature(),
+    ]
+}
+
+pub fn signal_signatures() -> Vec<MemberSignature> {
+    vec![
+        signal_signature::<AccessPointAdded, (AccessPoint,)>(),
+        signal_signature::<AccessPointChanged, (AccessPoint,)>(),
+        signal_signature::<AccessPointRemoved, (Path<'static>,)>(),
+        signal_signature::<WifiDeviceChanged, (WifiDevice,)>(),
+        signal_signature::<WifiDeviceReset, (Vec<WifiDevice>,)>(),
+    ]
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use dbus::Error;
+use tokio::sync::Notify;
+
+use crate::components::config::frontend::frontend_config;
+use crate::components::daemon::client::{config_duration, connection};
+use crate::components::daemon::proxy::base::{REGISTER_CLIENT, UNREGISTER_CLIENT};
+
+/// Used when no daemon timeout is configured.
+pub const DEFAULT_DAEMON_TIMEOUT: Duration = Duration::from_millis(5000);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const REGISTER_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Time ReSet waits for the daemon to become ready before giving up,
+/// configured in milliseconds via `daemon_timeout` in the `[dbus]` section of `frontend.toml`.
+pub fn daemon_timeout() -> Duration {
+    frontend_config()
+        .daemon_timeout
+        .unwrap_or_else(|| config_duration("daemon_timeout", DEFAULT_DAEMON_TIMEOUT))
+}
+
+/// Woken once the daemon is ready, see `mark_daemon_ready`.
+static DAEMON_READY: Notify = Notify::const_new();
+
+/// Sets the flag and wakes everyone waiting in `wait_for_daemon`.
+pub fn mark_daemon_ready(ready: &AtomicBool) {
+    ready.store(true, Ordering::SeqCst);
+    DAEMON_READY.notify_waiters();
+}
+
+/// Waits until the daemon is marked as ready or the timeout runs out, returning whether it is ready.
+pub async fn wait_for_daemon(ready: &AtomicBool, timeout: Duration) -> bool {
+    let notified = DAEMON_READY.notified();
+    tokio::pin!(notified);
+    // registered before checking the flag, so a notification in between is not missed
+    notified.as_mut().enable();
+    if ready.load(Ordering::SeqCst) {
+        return true;
+    }
+    tokio::time::timeout(timeout, notified).await.is_ok()
+}
+
+/// The embedded daemon only reports readiness through the flag it sets,
+/// this marks it as ready as soon as the flag is set.
+pub async fn watch_embedded_daemon(ready: &AtomicBool) {
+    while !ready.load(Ordering::SeqCst) {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    mark_daemon_ready(ready);
+}
+
+/// Failing is expected while the daemon starts, so unlike daemon calls of the pages
+/// this is neither logged nor shown.
+pub fn register_client() -> Result<(), Error> {
+    REGISTER_CLIENT
+        .call_on(&*connection()?, (String::from("ReSet"),), REGISTER_TIMEOUT)
+        .map(|_| ())
+}
+
+pub fn unregister_client() -> Result<(), Error> {
+    UNREGISTER_CLIENT
+        .call_on(&*connection()?, (String::from("ReSet"),), REGISTER_TIMEOUT)
+        .map(|_| ())
+}
+
+/// Used by the retry button of the daemon unavailable page.
+/// Either an external daemon appeared in the meantime, or the embedded one finished starting.
+pub fn retry_daemon(ready: &AtomicBool, timeout: Duration) -> bool {
+    if register_client().is_ok() {
+        mark_daemon_ready(ready);
+        return true;
+    }
+    // called on a gio worker thread, which has no tokio runtime to wait on
+    match tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+    {
+        Ok(runtime) => runtime.block_on(wait_for_daemon(ready, timeout)),
+        Err(_) => ready.load(Ordering::SeqCst),
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use dbus::blocking::Connection;
+use dbus::message::MatchRule;
+use gtk::gio;
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+
+use crate::components::base::utils::Listeners;
+use crate::components::utils::BASE;
+
+const DBUS_NAME: &str = "org.freedesktop.DBus";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DaemonEvent {
+    Vanished,
+    Appeared,
+}
+
+/// Watches `NameOwnerChanged` of the daemon name until the `daemon_watcher` flag is cleared,
+/// the handler is invoked on the main thread. Only one watcher runs per window.
+pub fn watch_daemon_name<F: Fn(DaemonEvent) + Clone + Send + Sync + 'static>(
+    listeners: Arc<Listeners>,
+    handler: F,
+) {
+    if listeners.daemon_watcher.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    gio::spawn_blocking(move || {
+        let conn = match Connection::new_session() {
+            Ok(conn) => conn,
+            Err(_) => {
+                ERROR!(
+                    "Could not connect to the session bus to watch the daemon",
+                    ErrorLevel::PartialBreakage
+                );
+                return;
+            }
+        };
+        let name_owner_changed =
+            MatchRule::new_signal(DBUS_NAME, "NameOwnerChanged").with_sender(DBUS_NAME);
+        let res = conn.add_match(
+            name_owner_changed,
+            move |(name, _, new_owner): (String, String, String), _, _| {
+                if name != BASE {
+                    return true;
+                }
+                let event = if new_owner.is_empty() {
+                    DaemonEvent::Vanished
+                } else {
+                    DaemonEvent::Appeared
+                };
+                let handler = handler.clone();
+                glib::MainContext::default().invoke(move || handler(event));
+                true
+            },
+        );
+        if res.is_err() {
+            ERROR!(
+                "Failed to watch the daemon name",
+                ErrorLevel::PartialBreakage
+            );
+            return;
+        }
+        while listeners.daemon_watcher.load(Ordering::SeqCst) {
+            let _ = conn.process(Duration::from_millis(1000));
+        }
+    });
+}
@@ -0,0 +1,387 @@
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use adw::prelude::{ActionRowExt, PreferencesGroupExt, PreferencesRowExt};
+use adw::{ActionRow, PreferencesGroup};
+use glib::Propagation;
+use gtk::prelude::*;
+use gtk::{gio, Align, GestureClick, Image, Label, Scale};
+use re_set_lib::audio::audio_structures::TAudioObject;
+use re_set_lib::bluetooth::bluetooth_structures::{BluetoothAdapter, BluetoothDevice};
+use re_set_lib::network::network_structures::{AccessPoint, WifiStrength};
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+
+use crate::components::audio::audio_entry::DBusFunction;
+use crate::components::audio::audio_utils::configure_volume_slider;
+use crate::components::audio::input::source_const;
+use crate::components::audio::output::sink_const;
+use crate::components::daemon::proxy::audio::VolumeArgs;
+use crate::components::daemon::proxy::bluetooth::BluetoothProxy;
+use crate::components::daemon::proxy::network::{NetworkProxy, GET_CURRENT_WIFI_DEVICE};
+use crate::components::daemon::proxy::{DaemonCall, DaemonClient};
+use crate::components::utils::Capabilities;
+
+const NETWORK_INTERFACES: &str = "/sys/class/net";
+
+/// State of the ethernet interfaces, which the daemon does not manage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WiredStatus {
+    /// Name of the first interface that has a link.
+    Connected(String),
+    Disconnected,
+    /// There is no ethernet interface.
+    Unavailable,
+}
+
+impl WiredStatus {
+    fn label(&self) -> String {
+        match self {
+            WiredStatus::Connected(interface) => format!("Connected via {}", interface),
+            WiredStatus::Disconnected => String::from("Cable unplugged"),
+            WiredStatus::Unavailable => String::from("No ethernet interface"),
+        }
+    }
+}
+
+enum WifiState {
+    Disabled,
+    Disconnected,
+    Connected(AccessPoint),
+}
+
+struct BluetoothState {
+    adapter: Option<BluetoothAdapter>,
+    connected_devices: Vec<BluetoothDevice>,
+}
+
+/// Cards of the home page, their rows are filled in once the daemon answered.
+/// Activating a row opens the full page of the setting.
+pub fn create_home_cards(capabilities: &Capabilities) -> Vec<PreferencesGroup> {
+    let mut cards = vec![create_network_card(capabilities.wifi.get())];
+    if capabilities.bluetooth.get() {
+        cards.push(create_bluetooth_card());
+    }
+    if capabilities.audio.get() {
+        cards.push(create_audio_card());
+    }
+    cards
+}
+
+/// Opens the page of the plugin that added a home page tile when the tile is clicked.
+pub fn open_plugin_on_click(tile: &impl IsA<gtk::Widget>, plugin_file: &str) {
+    let gesture = GestureClick::new();
+    let plugin_file = plugin_file.to_variant();
+    gesture.connect_released(move |gesture, _, _, _| {
+        if let Some(widget) = gesture.widget() {
+            let _ = widget.activate_action("win.open-plugin", Some(&plugin_file));
+        }
+    });
+    tile.add_controller(gesture);
+}
+
+fn create_network_card(wifi: bool) -> PreferencesGroup {
+    let group = PreferencesGroup::builder().title("Network").build();
+    if wifi {
+        let wifi_row = create_page_row("WiFi", "wifi", &[]);
+        load_wifi_state(wifi_row.clone());
+        group.add(&wifi_row);
+    }
+    let wired_row = ActionRow::builder()
+        .title("Wired")
+        .subtitle(wired_status().label())
+        .build();
+    wired_row.add_prefix(&Image::from_icon_name("network-wired-symbolic"));
+    group.add(&wired_row);
+    group
+}
+
+fn create_bluetooth_card() -> PreferencesGroup {
+    let group = PreferencesGroup::builder().title("Bluetooth").build();
+    let adapter_row = create_page_row("Bluetooth", "bluetooth", &[]);
+    adapter_row.add_prefix(&Image::from_icon_name("bluetooth-symbolic"));
+    group.add(&adapter_row);
+    load_bluetooth_state(group.clone(), adapter_row);
+    group
+}
+
+fn create_audio_card() -> PreferencesGroup {
+    let group = PreferencesGroup::builder().title("Audio").build();
+    group.add(&create_volume_row(
+        "Output",
+        "output",
+        sink_const::ICONS.active,
+        &sink_const::GETDEFAULT,
+        &sink_const::SETVOLUME,
+    ));
+    group.add(&create_volume_row(
+        "Input",
+        "input",
+        source_const::ICONS.active,
+        &source_const::GETDEFAULT,
+        &source_const::SETVOLUME,
+    ));
+    group
+}
+
+/// A row that opens the page with the given page name, see `Position::from_page_name`.
+/// The suffixes are shown in front of the arrow.
+fn create_page_row(title: &str, page: &str, suffixes: &[gtk::Widget]) -> ActionRow {
+    let row = ActionRow::builder()
+        .title(title)
+        .subtitle("Loading...")
+        .use_markup(false)
+        .activatable(true)
+        .build();
+    row.set_action_name(Some("win.navigate"));
+    row.set_action_target_value(Some(&page.to_variant()));
+    for suffix in suffixes {
+        row.add_suffix(suffix);
+    }
+    row.add_suffix(&Image::from_icon_name("go-next-symbolic"));
+    row
+}
+
+fn load_wifi_state(row: ActionRow) {
+    glib::spawn_future_local(async move {
+        let state = gio::spawn_blocking(get_wifi_state).await.ok().flatten();
+        let (icon, subtitle) = match state {
+            None => (
+                "network-wireless-offline-symbolic",
+                String::from("Unavailable"),
+            ),
+            Some(WifiState::Disabled) => {
+                ("network-wireless-disabled-symbolic", String::from("Off"))
+            }
+            Some(WifiState::Disconnected) => (
+                "network-wireless-offline-symbolic",
+                String::from("Not connected"),
+            ),
+            Some(WifiState::Connected(access_point)) => (
+                match WifiStrength::from_u8(access_point.strength) {
+                    WifiStrength::Excellent => "network-wireless-signal-excellent-symbolic",
+                    WifiStrength::Ok => "network-wireless-signal-ok-symbolic",
+                    WifiStrength::Weak => "network-wireless-signal-weak-symbolic",
+                    WifiStrength::None => "network-wireless-signal-none-symbolic",
+                },
+                format!(
+                    "{}, signal {}%",
+                    String::from_utf8_lossy(&access_point.ssid),
+                    access_point.strength
+                ),
+            ),
+        };
+        row.add_prefix(&Image::from_icon_name(icon));
+        row.set_subtitle(&subtitle);
+    });
+}
+
+/// Same data as the WiFi page, the access point of the current device is the connected one.
+fn get_wifi_state() -> Option<WifiState> {
+    let client = DaemonClient::new();
+    let enabled = match client.get_wifi_status() {
+        Ok(enabled) => enabled,
+        Err(_error) => {
+            ERROR!("Failed to get WiFi status", ErrorLevel::Recoverable);
+            return None;
+        }
+    };
+    if !enabled {
+        return Some(WifiState::Disabled);
+    }
+    let device = match client.call(&GET_CURRENT_WIFI_DEVICE, ()) {
+        Ok((device,)) => device,
+        Err(_error) => {
+            ERROR!("Failed to get current WiFi device", ErrorLevel::Recoverable);
+            return None;
+        }
+    };
+    let access_points = match client.list_access_points() {
+        Ok(access_points) => access_points,
+        Err(_error) => {
+            ERROR!("Failed to list access points", ErrorLevel::Recoverable);
+            return None;
+        }
+    };
+    Some(
+        access_points
+            .into_iter()
+            .find(|access_point| {
+                !access_point.ssid.is_empty() && access_point.ssid == device.active_access_point
+            })
+            .map_or(WifiState::Disconnected, WifiState::Connected),
+    )
+}
+
+fn load_bluetooth_state(group: PreferencesGroup, adapter_row: ActionRow) {
+    glib::spawn_future_local(async move {
+        let state = match gio::spawn_blocking(get_bluetooth_state)
+            .await
+            .ok()
+            .flatten()
+        {
+            Some(state) => state,
+            None => {
+                adapter_row.set_subtitle("Unavailable");
+                return;
+            }
+        };
+        match &state.adapter {
+            Some(adapter) => {
+                adapter_row.set_title(&adapter.alias);
+                adapter_row.set_subtitle(if adapter.powered { "On" } else { "Off" });
+            }
+            None => adapter_row.set_subtitle("No adapter"),
+        }
+        for device in state.connected_devices {
+            let row = create_page_row(&device.alias, "bluetooth", &[]);
+            row.set_subtitle("Connected");
+            if !device.icon.is_empty() {
+                row.add_prefix(&Image::from_icon_name(&device.icon));
+            }
+            group.add(&row);
+        }
+    });
+}
+
+/// Same data as the Bluetooth page, which uses the last adapter as well.
+fn get_bluetooth_state() -> Option<BluetoothState> {
+    let client = DaemonClient::new();
+    let adapters = match client.get_bluetooth_adapters() {
+        Ok(adapters) => adapters,
+        Err(_error) => {
+            ERROR!("Failed to get bluetooth adapters", ErrorLevel::Recoverable);
+            return None;
+        }
+    };
+    let devices = match client.get_bluetooth_devices() {
+        Ok(devices) => devices,
+        Err(_error) => {
+            ERROR!("Failed to get bluetooth devices", ErrorLevel::Recoverable);
+            return None;
+        }
+    };
+    Some(BluetoothState {
+        adapter: adapters.last().cloned(),
+        connected_devices: devices
+            .into_iter()
+            .filter(|device| device.connected)
+            .collect(),
+    })
+}
+
+/// A row with the default sink or source and a slider for its volume.
+fn create_volume_row<AudioObject: TAudioObject>(
+    title: &str,
+    page: &str,
+    icon: &str,
+    get_default: &'static DBusFunction<(), (AudioObject,)>,
+    set_volume: &'static DBusFunction<VolumeArgs, ()>,
+) -> ActionRow {
+    let percentage = Label::new(None);
+    let slider = Scale::builder()
+        .width_request(150)
+        .valign(Align::Center)
+        .sensitive(false)
+        .build();
+    configure_volume_slider(&slider);
+    let row = create_page_row(
+        title,
+        page,
+        &[slider.clone().upcast(), percentage.clone().upcast()],
+    );
+    row.add_prefix(&Image::from_icon_name(icon));
+
+    // index and channels of the default object, known once it was loaded
+    let target: Rc<Cell<Option<(u32, u16)>>> = Rc::new(Cell::new(None));
+    let time_stamp: Cell<Option<SystemTime>> = Cell::new(None);
+    let slider_target = target.clone();
+    let slider_percentage = percentage.clone();
+    slider.connect_change_value(move |_, _, value| {
+        let fraction = (value / 655.36).round();
+        slider_percentage.set_text(&(fraction.to_string() + "%"));
+        let (index, channels) = match slider_target.get() {
+            Some(target) => target,
+            None => return Propagation::Proceed,
+        };
+        if time_stamp
+            .get()
+            .is_some_and(|time| time.elapsed().unwrap_or_default() < Duration::from_millis(50))
+        {
+            return Propagation::Proceed;
+        }
+        time_stamp.set(Some(SystemTime::now()));
+        if let Err(_error) =
+            DaemonClient::new().call(&set_volume.function, (index, channels, value as u32))
+        {
+            ERROR!(set_volume.error, ErrorLevel::Recoverable);
+        }
+        Propagation::Proceed
+    });
+
+    let loaded_row = row.clone();
+    glib::spawn_future_local(async move {
+        let default =
+            gio::spawn_blocking(move || DaemonClient::new().call(&get_default.function, ())).await;
+        match default {
+            Ok(Ok((audio_object,))) => {
+                let volume = *audio_object.volume().first().unwrap_or(&0_u32);
+                let fraction = (volume as f64 / 655.36).round();
+                percentage.set_text(&(fraction.to_string() + "%"));
+                slider.set_value(volume as f64);
+                slider.set_sensitive(true);
+                target.set(Some((audio_object.index(), audio_object.channels())));
+                loaded_row.set_subtitle(&audio_object.alias());
+            }
+            _ => {
+                ERROR!(get_default.error, ErrorLevel::Recoverable);
+                loaded_row.set_subtitle("Unavailable");
+            }
+        }
+    });
+    row
+}
+
+fn wired_status() -> WiredStatus {
+    wired_status_in(Path::new(NETWORK_INTERFACES))
+}
+
+/// Reads the link state of the ethernet interfaces in a directory like `/sys/class/net`.
+pub fn wired_status_in(interfaces: &Path) -> WiredStatus {
+    let entries = match fs::read_dir(interfaces) {
+        Ok(entries) => entries,
+        Err(_) => return WiredStatus::Unavailable,
+    };
+    let mut ethernet: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|interface| is_ethernet(interface))
+        .collect();
+    if ethernet.is_empty() {
+        return WiredStatus::Unavailable;
+    }
+    ethernet.sort();
+    for interface in ethernet {
+        // reading the carrier of an interface that is down fails
+        let carrier = fs::read_to_string(interface.join("carrier")).unwrap_or_default();
+        if carrier.trim() == "1" {
+            let name = interface.file_name().unwrap_or_default();
+            return WiredStatus::Connected(name.to_string_lossy().into_owned());
+        }
+    }
+    WiredStatus::Disconnected
+}
+
+/// Physical interfaces of the ethernet type that are not wireless.
+/// Virtual interfaces like bridges or the loopback device have no `device`.
+fn is_ethernet(interface: &Path) -> bool {
+    let interface_type = fs::read_to_string(interface.join("type")).unwrap_or_default();
+    interface_type.trim() == "1"
+        && interface.join("device").exists()
+        && !interface.join("wireless").exists()
+        && !interface.join("phy80211").exists()
+}
@@ -0,0 +1 @@
+pub mod home_page;
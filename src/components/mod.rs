@@ -1,6 +1,9 @@
 pub mod audio;
 pub mod base;
 pub mod bluetooth;
+pub mod config;
+pub mod daemon;
+pub mod home;
 mod plugin;
 pub mod utils;
 pub mod wifi;
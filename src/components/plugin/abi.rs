@@ -0,0 +1,163 @@
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+
+use reset_plugin_api::entry::{PanicTaker, PLUGIN_PANIC_SYMBOL};
+
+use super::manager::{call_plugin, open_loaded_library};
+
+/// Version of the contract between ReSet and frontend plugins,
+/// increased whenever the exported functions or their types change.
+/// Version 3 passes the `PluginHost` of reset_plugin_api to `frontend_host`,
+/// version 4 requires the entry points to be exported with `frontend_entries!` of
+/// reset_plugin_api, which catches their panics inside the plugin.
+pub const PLUGIN_ABI_VERSION: u32 = 4;
+/// Plugins export `fn frontend_abi() -> *const c_char`, returning a static
+/// declaration like `abi=4;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7`.
+pub const PLUGIN_ABI_SYMBOL: &[u8] = b"frontend_abi";
+/// Versions of the libraries whose types cross the plugin boundary, kept in sync with Cargo.toml.
+pub const RE_SET_LIB_VERSION: &str = "5.2.5";
+pub const GTK_VERSION: &str = "0.8.1";
+/// The `PluginHost` passes the dbus connection of ReSet.
+pub const DBUS_VERSION: &str = "0.9.7";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginAbi {
+    pub abi_version: u32,
+    pub re_set_lib_version: String,
+    pub gtk_version: String,
+    pub dbus_version: String,
+}
+
+impl PluginAbi {
+    /// The ABI of this build of ReSet.
+    pub fn current() -> Self {
+        Self {
+            abi_version: PLUGIN_ABI_VERSION,
+            re_set_lib_version: String::from(RE_SET_LIB_VERSION),
+            gtk_version: String::from(GTK_VERSION),
+            dbus_version: String::from(DBUS_VERSION),
+        }
+    }
+
+    pub fn parse(declaration: &str) -> Result<Self, String> {
+        let mut abi_version = None;
+        let mut re_set_lib_version = None;
+        let mut gtk_version = None;
+        let mut dbus_version = None;
+        for entry in declaration.split(';').filter(|entry| !entry.is_empty()) {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("invalid ABI entry {}", entry))?;
+            match key.trim() {
+                "abi" => {
+                    abi_version = Some(
+                        value
+                            .trim()
+                            .parse::<u32>()
+                            .map_err(|_| format!("invalid ABI version {}", value))?,
+                    )
+                }
+                "re_set-lib" => re_set_lib_version = Some(value.trim().to_string()),
+                "gtk4" => gtk_version = Some(value.trim().to_string()),
+                "dbus" => dbus_version = Some(value.trim().to_string()),
+                // unknown entries are left for newer versions of ReSet
+                _ => (),
+            }
+        }
+        Ok(Self {
+            abi_version: abi_version.ok_or("missing abi entry")?,
+            re_set_lib_version: re_set_lib_version.ok_or("missing re_set-lib entry")?,
+            gtk_version: gtk_version.ok_or("missing gtk4 entry")?,
+            dbus_version: dbus_version.ok_or("missing dbus entry")?,
+        })
+    }
+
+    /// Checks whether a plugin with this ABI can be used by the given build of ReSet.
+    pub fn check(&self, host: &PluginAbi) -> Result<(), String> {
+        if self.abi_version != host.abi_version {
+            return Err(format!(
+                "built for plugin ABI {}, ReSet uses {}",
+                self.abi_version, host.abi_version
+            ));
+        }
+        if !semver_compatible(&self.re_set_lib_version, &host.re_set_lib_version) {
+            return Err(format!(
+                "built against re_set-lib {}, ReSet uses {}",
+                self.re_set_lib_version, host.re_set_lib_version
+            ));
+        }
+        if !semver_compatible(&self.gtk_version, &host.gtk_version) {
+            return Err(format!(
+                "built against gtk4 {}, ReSet uses {}",
+                self.gtk_version, host.gtk_version
+            ));
+        }
+        if !semver_compatible(&self.dbus_version, &host.dbus_version) {
+            return Err(format!(
+                "built against dbus {}, ReSet uses {}",
+                self.dbus_version, host.dbus_version
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Same rules as cargo, versions are compatible if their first non-zero component matches.
+fn semver_compatible(left: &str, right: &str) -> bool {
+    let components = |version: &str| -> Option<Vec<u64>> {
+        version
+            .split('.')
+            .map(|component| component.parse::<u64>().ok())
+            .collect()
+    };
+    let (left, right) = match (components(left), components(right)) {
+        (Some(left), Some(right)) => (left, right),
+        _ => return false,
+    };
+    for (left, right) in left.iter().zip(right.iter()) {
+        if left != right {
+            return false;
+        }
+        if *left != 0 {
+            return true;
+        }
+    }
+    left.len() == right.len()
+}
+
+/// Reads the ABI declaration of a loaded plugin library and checks it against this build.
+/// Nothing else of the plugin may be called if this fails, as the types would not match
+/// or a panic could not be caught.
+/// re_set-lib already called `capabilities` while loading the library, before this check runs,
+/// so an incompatible plugin may have returned a mismatching `PluginCapabilities` by then.
+pub fn check_plugin_abi(path: &Path) -> Result<(), String> {
+    let library = open_loaded_library(path).ok_or("could not access the plugin library")?;
+    let (declaration, take_panic) = unsafe {
+        let frontend_abi = library
+            .get::<unsafe extern "C" fn() -> *const c_char>(PLUGIN_ABI_SYMBOL)
+            .map_err(|_| {
+                format!(
+                    "does not declare its plugin ABI, ReSet requires frontend_abi for ABI {}",
+                    PLUGIN_ABI_VERSION
+                )
+            })?;
+        let take_panic = library
+            .get::<PanicTaker>(PLUGIN_PANIC_SYMBOL)
+            .ok()
+            .map(|take_panic| *take_panic);
+        let declaration = call_plugin("frontend_abi", take_panic, || frontend_abi())
+            .map_err(|error| error.to_string())?;
+        if declaration.is_null() {
+            return Err(String::from("returned no ABI declaration"));
+        }
+        let declaration = CStr::from_ptr(declaration).to_string_lossy().to_string();
+        (declaration, take_panic)
+    };
+    PluginAbi::parse(&declaration)?.check(&PluginAbi::current())?;
+    if take_panic.is_none() {
+        return Err(String::from(
+            "does not export its entry points with frontend_entries! of reset_plugin_api",
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use std::cell::RefCell;
+use std::path::Path;
+
+use gtk::prelude::*;
+
+use super::manager::{call_plugin, open_loaded_library, panic_taker, PluginError};
+
+/// Plugins may export `fn frontend_extensions() -> Vec<PageExtension>`
+/// to add widgets to the built-in pages.
+pub const PLUGIN_EXTENSIONS_SYMBOL: &[u8] = b"frontend_extensions";
+
+/// A widget a plugin adds to a built-in page, plugins declare an identical struct.
+#[repr(C)]
+pub struct PageExtension {
+    /// Name of the [`ExtensionPoint`], e.g. `sink_box`.
+    pub target: String,
+    /// Index among the built-in content, `-1` appends the widget.
+    pub position: i32,
+    /// The widget is only shown if the daemon provides all of these capabilities.
+    pub required_capabilities: Vec<String>,
+    pub widget: gtk::Box,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionPoint {
+    /// Cards next to the output and input of `HANDLE_AUDIO_CLICK`.
+    AudioPage,
+    /// Cards next to WiFi and Bluetooth of `HANDLE_CONNECTIVITY_CLICK`.
+    ConnectivityPage,
+    /// Groups on the main page of the `SinkBox`.
+    SinkBox,
+    /// Groups on the main page of the `WifiBox`.
+    WifiBox,
+    /// Groups on the main page of the `BluetoothBox`.
+    BluetoothBox,
+    /// Summary tiles of `HANDLE_HOME`, clicking one opens the page of its plugin.
+    HomePage,
+}
+
+impl ExtensionPoint {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "audio_page" => Some(ExtensionPoint::AudioPage),
+            "connectivity_page" => Some(ExtensionPoint::ConnectivityPage),
+            "sink_box" => Some(ExtensionPoint::SinkBox),
+            "wifi_box" => Some(ExtensionPoint::WifiBox),
+            "bluetooth_box" => Some(ExtensionPoint::BluetoothBox),
+            "home_page" => Some(ExtensionPoint::HomePage),
+            _ => None,
+        }
+    }
+}
+
+struct RegisteredExtension {
+    plugin_file: String,
+    point: ExtensionPoint,
+    position: i32,
+    required_capabilities: Vec<String>,
+    widget: gtk::Box,
+}
+
+struct ExtensionRegistry {
+    extensions: Vec<RegisteredExtension>,
+    /// File names of the plugins whose extensions are shown.
+    enabled_plugins: Vec<String>,
+    daemon_capabilities: Vec<String>,
+}
+
+thread_local! {
+    // widgets may only be used on the main thread
+    static EXTENSIONS: RefCell<ExtensionRegistry> = const {
+        RefCell::new(ExtensionRegistry {
+            extensions: Vec::new(),
+            enabled_plugins: Vec::new(),
+            daemon_capabilities: Vec::new(),
+        })
+    };
+}
+
+/// Asks a started plugin for its extensions, plugins without `frontend_extensions` have none.
+pub fn plugin_extensions(path: &Path) -> Result<Vec<PageExtension>, PluginError> {
+    let library = match open_loaded_library(path) {
+        Some(library) => library,
+        None => return Ok(Vec::new()),
+    };
+    unsafe {
+        match library.get::<unsafe extern "C" fn() -> Vec<PageExtension>>(PLUGIN_EXTENSIONS_SYMBOL)
+        {
+            Ok(frontend_extensions) => {
+                call_plugin("frontend_extensions", panic_taker(path), || {
+                    frontend_extensions()
+                })
+            }
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Registers the extensions of a started plugin, either all of them or none.
+pub fn register_extensions(
+    plugin_file: &str,
+    extensions: Vec<PageExtension>,
+) -> Result<(), PluginError> {
+    let mut registered = Vec::new();
+    for extension in extensions {
+        let point = ExtensionPoint::from_name(&extension.target).ok_or_else(|| {
+            PluginError::new(
+                "frontend_extensions",
+                format!("unknown extension point {}", extension.target),
+            )
+        })?;
+        registered.push(RegisteredExtension {
+            plugin_file: plugin_file.to_string(),
+            point,
+            position: extension.position,
+            required_capabilities: extension.required_capabilities,
+            widget: extension.widget,
+        });
+    }
+    EXTENSIONS.with(|registry| registry.borrow_mut().extensions.extend(registered));
+    Ok(())
+}
+
+/// Removes the extensions of a plugin from the pages, e.g. before reloading it.
+pub fn unregister_extensions(plugin_file: &str) {
+    let removed: Vec<gtk::Box> = EXTENSIONS.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut registry.extensions)
+            .into_iter()
+            .partition(|extension| extension.plugin_file == plugin_file);
+        registry.extensions = kept;
+        removed
+            .into_iter()
+            .map(|extension| extension.widget)
+            .collect()
+    });
+    for widget in &removed {
+        detach(widget);
+    }
+}
+
+/// Extensions of other plugins are left out the next time a page is built.
+pub fn set_enabled_extension_plugins(plugin_files: Vec<String>) {
+    EXTENSIONS.with(|registry| registry.borrow_mut().enabled_plugins = plugin_files);
+}
+
+/// Extensions requiring other capabilities are left out the next time a page is built.
+pub fn set_extension_capabilities(daemon_capabilities: &[String]) {
+    EXTENSIONS.with(|registry| {
+        registry.borrow_mut().daemon_capabilities = daemon_capabilities.to_vec();
+    });
+}
+
+/// The widgets to show at an extension point with the index to insert them at, in order.
+/// The widgets are removed from the page they were shown on before.
+pub fn extension_widgets(point: ExtensionPoint) -> Vec<(i32, gtk::Box)> {
+    plugin_extension_widgets(point)
+        .into_iter()
+        .map(|(index, _, widget)| (index, widget))
+        .collect()
+}
+
+/// Same as [`extension_widgets`], with the file of the plugin that added each widget.
+pub fn plugin_extension_widgets(point: ExtensionPoint) -> Vec<(i32, String, gtk::Box)> {
+    let (positions, widgets): (Vec<i32>, Vec<(String, gtk::Box)>) = EXTENSIONS.with(|registry| {
+        let registry = registry.borrow();
+        registry
+            .extensions
+            .iter()
+            .filter(|extension| {
+                extension.point == point
+                    && registry.enabled_plugins.contains(&extension.plugin_file)
+                    && extension
+                        .required_capabilities
+                        .iter()
+                        .all(|capability| registry.daemon_capabilities.contains(capability))
+            })
+            .map(|extension| {
+                (
+                    extension.position,
+                    (extension.plugin_file.clone(), extension.widget.clone()),
+                )
+            })
+            .unzip()
+    });
+    for (_, widget) in &widgets {
+        detach(widget);
+    }
+    insertion_order(&positions)
+        .into_iter()
+        .map(|(extension, index)| {
+            let (plugin_file, widget) = widgets[extension].clone();
+            (index, plugin_file, widget)
+        })
+        .collect()
+}
+
+/// Sorts extensions by position and turns their positions into indices for inserting them
+/// one after another, so that each position refers to the built-in content.
+/// Returns the index of the extension and the index to insert it at.
+pub fn insertion_order(positions: &[i32]) -> Vec<(usize, i32)> {
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    // appended extensions keep their order at the end
+    order.sort_by_key(|extension| match positions[*extension] {
+        position if position < 0 => i32::MAX,
+        position => position,
+    });
+    let mut inserted = 0;
+    order
+        .into_iter()
+        .map(|extension| {
+            let position = positions[extension];
+            if position < 0 {
+                return (extension, -1);
+            }
+            let index = position.saturating_add(inserted);
+            inserted += 1;
+            (extension, index)
+        })
+        .collect()
+}
+
+/// Adds the extensions of a built-in box to its container.
+pub fn insert_extensions(point: ExtensionPoint, container: &gtk::Box) {
+    for (index, widget) in extension_widgets(point) {
+        let sibling = if index < 0 {
+            container.last_child()
+        } else {
+            child_before(container, index)
+        };
+        container.insert_child_after(&widget, sibling.as_ref());
+    }
+}
+
+/// The child after which a widget has to be inserted to end up at `index`,
+/// indices past the end result in the last child.
+fn child_before(container: &gtk::Box, index: i32) -> Option<gtk::Widget> {
+    if index == 0 {
+        return None;
+    }
+    let mut child = container.first_child()?;
+    for _ in 1..index {
+        match child.next_sibling() {
+            Some(next) => child = next,
+            None => break,
+        }
+    }
+    Some(child)
+}
+
+fn detach(widget: &gtk::Box) {
+    if let Some(parent) = widget.parent() {
+        match parent.downcast_ref::<gtk::Box>() {
+            Some(parent) => parent.remove(widget),
+            None => widget.unparent(),
+        }
+    }
+}
@@ -4,6 +4,8 @@ use std::sync::Arc;
 
 use gtk::FlowBox;
 
+use crate::components::daemon::compat::DaemonMethod;
+use crate::components::plugin::search::SearchKeyword;
 use crate::components::{base::utils::{Listeners, Position}, utils::Capabilities};
 
 // extern "C" {
@@ -22,6 +24,7 @@ pub trait TSideBarInfo {
     fn regular_click_event(&self) -> Option<RegularClickEvent>;
     fn plugin_click_event(&self) -> PluginClickEvent;
     fn plugin_boxes(&self) -> Option<Vec<gtk::Box>>;
+    fn search_keywords(&self) -> Vec<SearchKeyword>;
 }
 
 pub struct ReSetSidebarInfo {
@@ -31,6 +34,9 @@ pub struct ReSetSidebarInfo {
     // pub pre_click: 
     pub click_event: RegularClickEvent,
     // pub post_click: 
+    pub required_methods: &'static [DaemonMethod],
+    /// Keywords the search finds the page by, with the title of the row they refer to.
+    pub search_keywords: &'static [(&'static str, Option<&'static str>)],
 }
 
 impl TSideBarInfo for ReSetSidebarInfo {
@@ -57,6 +63,13 @@ impl TSideBarInfo for ReSetSidebarInfo {
     fn plugin_boxes(&self) -> Option<Vec<gtk::Box>> {
         None
     }
+
+    fn search_keywords(&self) -> Vec<SearchKeyword> {
+        self.search_keywords
+            .iter()
+            .map(|(keyword, row)| SearchKeyword::new(keyword, *row))
+            .collect()
+    }
 }
 
 #[repr(C)]
@@ -66,6 +79,9 @@ pub struct PluginSidebarInfo {
     pub parent: Option<&'static str>,
     pub click_event: PluginClickEvent,
     pub plugin_boxes: Vec<gtk::Box>,
+    pub plugin_name: String,
+    pub plugin_file: String,
+    pub search_keywords: Vec<SearchKeyword>,
 }
 
 impl TSideBarInfo for PluginSidebarInfo {
@@ -92,4 +108,8 @@ impl TSideBarInfo for PluginSidebarInfo {
     fn plugin_boxes(&self) -> Option<Vec<gtk::Box>> {
         Some(self.plugin_boxes.clone())
     }
+
+    fn search_keywords(&self) -> Vec<SearchKeyword> {
+        self.search_keywords.clone()
+    }
 }
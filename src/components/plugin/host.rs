@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::mem::size_of;
+use std::path::Path;
+use std::thread;
+
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use glib::WeakRef;
+use gtk::gio;
+use gtk::prelude::ObjectExt;
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+use reset_plugin_api::{
+    BlockingWork, MainLoopUpdate, PluginHost, PluginListener, PLUGIN_HOST_VERSION,
+};
+
+use crate::components::daemon::client::connection;
+use crate::components::window::reset_window::ReSetWindow;
+
+use super::manager::{call_plugin, open_loaded_library, panic_taker, PluginError};
+
+/// Plugins may export `fn frontend_host(host: &'static PluginHost)`,
+/// which is called before `frontend_startup`.
+/// [`PluginHost`] is declared in the reset_plugin_api crate, which plugins depend on as well.
+pub const PLUGIN_HOST_SYMBOL: &[u8] = b"frontend_host";
+
+pub static PLUGIN_HOST: PluginHost = PluginHost {
+    version: PLUGIN_HOST_VERSION,
+    size: size_of::<PluginHost>(),
+    connection,
+    show_banner,
+    show_error,
+    spawn_blocking,
+    invoke_on_main,
+    register_listener,
+};
+
+thread_local! {
+    // the window plugins are shown in, only accessed on the main thread
+    static HOST_WINDOW: RefCell<Option<WeakRef<ReSetWindow>>> = const { RefCell::new(None) };
+}
+
+pub fn set_host_window(window: &ReSetWindow) {
+    HOST_WINDOW.with(|host_window| *host_window.borrow_mut() = Some(window.downgrade()));
+}
+
+/// Hands the host services to a plugin, plugins without `frontend_host` do not use them.
+pub fn provide_host(path: &Path) -> Result<(), PluginError> {
+    let library = match open_loaded_library(path) {
+        Some(library) => library,
+        None => return Ok(()),
+    };
+    unsafe {
+        match library.get::<unsafe extern "C" fn(&'static PluginHost)>(PLUGIN_HOST_SYMBOL) {
+            Ok(frontend_host) => call_plugin("frontend_host", panic_taker(path), || {
+                frontend_host(&PLUGIN_HOST)
+            }),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Runs the action on the main loop if the window still exists, callable from any thread.
+fn with_window(action: impl FnOnce(&ReSetWindow) + Send + 'static) {
+    glib::MainContext::default().invoke(move || {
+        let window = HOST_WINDOW.with(|host_window| {
+            host_window
+                .borrow()
+                .as_ref()
+                .and_then(|window| window.upgrade())
+        });
+        if let Some(window) = window {
+            action(&window);
+        }
+    });
+}
+
+fn show_banner(message: &str) {
+    let message = message.to_string();
+    with_window(move |window| window.show_banner(&message));
+}
+
+fn show_error(message: &str) {
+    ERROR!(
+        format!("Plugin error: {}", message),
+        ErrorLevel::Recoverable
+    );
+    show_banner(message);
+}
+
+/// The update is not `Send`, it can therefore only be passed on by the thread running the main loop.
+fn spawn_blocking(work: BlockingWork, update: MainLoopUpdate) -> Result<(), String> {
+    let context = glib::MainContext::default();
+    if !context.is_owner() {
+        return Err(String::from(
+            "spawn_blocking has to be called from the main thread",
+        ));
+    }
+    context.spawn_local(async move {
+        match gio::spawn_blocking(work).await {
+            Ok(result) => update(result),
+            Err(_) => ERROR!(
+                "Blocking work of a plugin panicked",
+                ErrorLevel::Recoverable
+            ),
+        }
+    });
+    Ok(())
+}
+
+fn invoke_on_main(function: Box<dyn FnOnce() + Send>) {
+    glib::MainContext::default().invoke(function);
+}
+
+fn register_listener(listener: PluginListener) {
+    with_window(move |window| {
+        let active = window.imp().listeners.add_plugin_listener();
+        thread::spawn(move || listener(active));
+    });
+}
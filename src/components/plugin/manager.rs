@@ -0,0 +1,359 @@
+use std::ffi::{c_void, CStr};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError, RwLock};
+
+use re_set_lib::utils::plugin::{PluginCapabilities, PluginImplementation, SidebarInfo};
+use re_set_lib::utils::plugin_setup::{FrontendPluginFunctions, FRONTEND_PLUGINS};
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+use reset_plugin_api::entry::{PanicTaker, PLUGIN_PANIC_SYMBOL};
+
+// rebuilt whenever the plugins are loaded, read by the plugins page
+static PLUGINS: RwLock<Vec<PluginInfo>> = RwLock::new(Vec::new());
+// file names of plugins that failed or were refused, they stay disabled until ReSet is restarted
+static SESSION_DISABLED_PLUGINS: Mutex<Vec<(String, PluginStatus)>> = Mutex::new(Vec::new());
+// file names of plugins whose frontend_startup was called, they are shut down on exit
+static STARTED_PLUGINS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Plugins may export `fn frontend_enter()` and `fn frontend_leave()`,
+/// called when their page is shown and when it is left, e.g. to start and stop listeners.
+pub const PLUGIN_ENTER_SYMBOL: &[u8] = b"frontend_enter";
+pub const PLUGIN_LEAVE_SYMBOL: &[u8] = b"frontend_leave";
+
+pub type PluginHook = unsafe extern "C" fn();
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PluginStatus {
+    Loaded,
+    Disabled,
+    /// The plugin requires capabilities the daemon does not provide.
+    MissingCapabilities,
+    /// An entry point of the plugin panicked or returned unusable data.
+    Failed(PluginError),
+    /// The plugin was built for a different plugin ABI, it is never called.
+    Incompatible(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginError {
+    pub function: &'static str,
+    pub message: String,
+}
+
+impl PluginError {
+    pub fn new(function: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            function,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} failed: {}", self.function, self.message)
+    }
+}
+
+/// Calls a plugin entry point and turns a panic into an error, so that a faulty plugin
+/// cannot take down ReSet.
+/// A plugin links its own std, a panic unwinding from it into ReSet would abort. The entry points
+/// exported with `frontend_entries!` of reset_plugin_api therefore catch it inside the plugin and
+/// return a fallback value, the message is taken from the plugin right after the call.
+pub fn call_plugin<T>(
+    function: &'static str,
+    take_panic: Option<PanicTaker>,
+    call: impl FnOnce() -> T,
+) -> Result<T, PluginError> {
+    let result = call();
+    match take_panic.and_then(caught_panic) {
+        Some(message) => Err(PluginError::new(function, message)),
+        None => Ok(result),
+    }
+}
+
+/// Looks up the [`PanicTaker`] of an already loaded library, every compatible plugin exports it.
+pub fn panic_taker(path: &Path) -> Option<PanicTaker> {
+    let library = open_loaded_library(path)?;
+    unsafe {
+        library
+            .get::<PanicTaker>(PLUGIN_PANIC_SYMBOL)
+            .ok()
+            .map(|take_panic| *take_panic)
+    }
+}
+
+/// The message of the last panic a plugin caught, if it did not report it yet.
+pub fn caught_panic(take_panic: PanicTaker) -> Option<String> {
+    unsafe {
+        let message = take_panic();
+        if message.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(message).to_string_lossy().to_string())
+    }
+}
+
+/// Disables a failed or incompatible plugin for the rest of the session.
+pub fn disable_for_session(file_name: &str, status: PluginStatus) {
+    match &status {
+        PluginStatus::Failed(_error) => ERROR!(
+            format!("Plugin {} disabled, {}", file_name, _error),
+            ErrorLevel::PartialBreakage
+        ),
+        PluginStatus::Incompatible(_message) => ERROR!(
+            format!("Plugin {} refused, {}", file_name, _message),
+            ErrorLevel::PartialBreakage
+        ),
+        _ => (),
+    }
+    SESSION_DISABLED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push((file_name.to_string(), status));
+}
+
+pub fn session_status(file_name: &str) -> Option<PluginStatus> {
+    SESSION_DISABLED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(disabled, _)| disabled == file_name)
+        .map(|(_, status)| status.clone())
+}
+
+/// Allows loading a plugin again that was disabled for the session, e.g. after a new build.
+pub fn enable_for_session(file_name: &str) {
+    SESSION_DISABLED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|(disabled, _)| disabled != file_name);
+}
+
+/// Remembers a started plugin for [`shutdown_plugins`].
+pub fn mark_started(file_name: &str) {
+    STARTED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(file_name.to_string());
+}
+
+/// Calls `frontend_shutdown` of every started plugin, including plugins disabled since then.
+pub fn shutdown_plugins() {
+    let started = std::mem::take(
+        &mut *STARTED_PLUGINS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    unsafe {
+        for plugin in FRONTEND_PLUGINS.iter() {
+            let plugin_file = plugin_library(plugin)
+                .as_deref()
+                .map(library_file_name)
+                .unwrap_or_default();
+            if started.contains(&plugin_file) {
+                shutdown_plugin_functions(plugin, &plugin_file);
+            }
+        }
+    }
+}
+
+/// Calls `frontend_shutdown` of a single plugin if it was started, e.g. before reloading it.
+pub fn shutdown_plugin(file_name: &str) {
+    {
+        let mut started = STARTED_PLUGINS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !started.iter().any(|started| started == file_name) {
+            return;
+        }
+        started.retain(|started| started != file_name);
+    }
+    unsafe {
+        let plugin = FRONTEND_PLUGINS.iter().find(|plugin| {
+            plugin_library(plugin)
+                .as_deref()
+                .map(library_file_name)
+                .is_some_and(|plugin_file| plugin_file == file_name)
+        });
+        if let Some(plugin) = plugin {
+            shutdown_plugin_functions(plugin, file_name);
+        }
+    }
+}
+
+fn shutdown_plugin_functions(plugin: &FrontendPluginFunctions, plugin_file: &str) {
+    let take_panic = plugin_library(plugin).as_deref().and_then(panic_taker);
+    if let Err(_error) = call_plugin("frontend_shutdown", take_panic, || unsafe {
+        (plugin.frontend_shutdown)()
+    }) {
+        ERROR!(
+            format!("Plugin {} {}", plugin_file, _error),
+            ErrorLevel::Recoverable
+        );
+    }
+}
+
+/// The optional page hooks of a plugin.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PluginHooks {
+    pub enter: Option<PluginHook>,
+    pub leave: Option<PluginHook>,
+    pub take_panic: Option<PanicTaker>,
+}
+
+/// Looks up the page hooks of an already loaded library.
+/// The hooks stay valid after the handle is dropped, as re_set-lib never unloads plugins.
+pub fn plugin_hooks(path: &Path) -> PluginHooks {
+    let library = match open_loaded_library(path) {
+        Some(library) => library,
+        None => return PluginHooks::default(),
+    };
+    unsafe {
+        let hook = |symbol: &[u8]| library.get::<PluginHook>(symbol).ok().map(|hook| *hook);
+        PluginHooks {
+            enter: hook(PLUGIN_ENTER_SYMBOL),
+            leave: hook(PLUGIN_LEAVE_SYMBOL),
+            take_panic: panic_taker(path),
+        }
+    }
+}
+
+/// Page hooks are called while the page is shown, a failing hook is therefore only logged.
+pub fn call_hook(function: &'static str, hook: PluginHook, take_panic: Option<PanicTaker>) {
+    if let Err(_error) = call_plugin(function, take_panic, || unsafe { hook() }) {
+        ERROR!(format!("Plugin {}", _error), ErrorLevel::Recoverable);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluginKind {
+    Frontend,
+    Backend,
+    Both,
+    /// Described by a manifest instead of a library.
+    Manifest,
+}
+
+impl PluginKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PluginKind::Frontend => "Frontend",
+            PluginKind::Backend => "Backend",
+            PluginKind::Both => "Frontend and backend",
+            PluginKind::Manifest => "Manifest",
+        }
+    }
+}
+
+impl From<&PluginImplementation> for PluginKind {
+    fn from(implementation: &PluginImplementation) -> Self {
+        match implementation {
+            PluginImplementation::Frontend => PluginKind::Frontend,
+            PluginImplementation::Backend => PluginKind::Backend,
+            PluginImplementation::Both => PluginKind::Both,
+        }
+    }
+}
+
+/// Everything the plugins page shows about a frontend plugin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginInfo {
+    /// File name of the library, identifies the plugin without calling into it.
+    pub file_name: String,
+    /// Only known once the plugin was asked for it, which disabled plugins are not.
+    pub name: Option<String>,
+    pub capabilities: Vec<String>,
+    pub requires_capabilities: bool,
+    pub capabilities_satisfied: bool,
+    pub kind: Option<PluginKind>,
+    pub status: PluginStatus,
+}
+
+impl PluginInfo {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.file_name)
+    }
+}
+
+pub fn set_plugin_infos(plugins: Vec<PluginInfo>) {
+    *PLUGINS.write().unwrap_or_else(PoisonError::into_inner) = plugins;
+}
+
+pub fn plugin_infos() -> Vec<PluginInfo> {
+    PLUGINS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Path of the library a plugin was loaded from, found via the address of one of its functions.
+pub fn plugin_library(plugin: &FrontendPluginFunctions) -> Option<PathBuf> {
+    let address = *plugin.frontend_name as *const c_void;
+    let mut info = libc::Dl_info {
+        dli_fname: std::ptr::null(),
+        dli_fbase: std::ptr::null_mut(),
+        dli_sname: std::ptr::null(),
+        dli_saddr: std::ptr::null_mut(),
+    };
+    unsafe {
+        if libc::dladdr(address, &mut info) == 0 || info.dli_fname.is_null() {
+            return None;
+        }
+        let path = CStr::from_ptr(info.dli_fname).to_str().ok()?;
+        Some(PathBuf::from(path))
+    }
+}
+
+pub fn library_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Opens a library that re_set-lib already loaded, never loads a new one.
+pub fn open_loaded_library(path: &Path) -> Option<libloading::os::unix::Library> {
+    unsafe {
+        libloading::os::unix::Library::open(Some(path), libc::RTLD_NOLOAD | libc::RTLD_LAZY).ok()
+    }
+}
+
+/// Asks an already loaded library for its implementation kind,
+/// the frontend plugin functions only keep the capabilities.
+pub fn plugin_kind(path: &Path) -> Option<PluginKind> {
+    let library = open_loaded_library(path)?;
+    unsafe {
+        let capabilities = library
+            .get::<unsafe extern "C" fn() -> PluginCapabilities>(b"capabilities")
+            .ok()?;
+        let capabilities = call_plugin("capabilities", panic_taker(path), || capabilities()).ok()?;
+        Some(PluginKind::from(capabilities.get_implementation()))
+    }
+}
+
+/// Rejects sidebar data that would break the sidebar, `taken_names` are the entries that
+/// already exist.
+pub fn validate_plugin_data(
+    sidebar_info: &SidebarInfo,
+    plugin_boxes: &[gtk::Box],
+    taken_names: &[&str],
+) -> Result<(), PluginError> {
+    if sidebar_info.name.trim().is_empty() {
+        return Err(PluginError::new(
+            "frontend_data",
+            "returned an empty sidebar name",
+        ));
+    }
+    if taken_names.contains(&sidebar_info.name) {
+        return Err(PluginError::new(
+            "frontend_data",
+            format!("sidebar name {} is already in use", sidebar_info.name),
+        ));
+    }
+    if plugin_boxes.is_empty() {
+        return Err(PluginError::new("frontend_data", "returned no widgets"));
+    }
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use std::fs;
+use std::path::PathBuf;
+
+use dbus::strings::{BusName, Interface, Member, Path};
+use re_set_lib::create_config_directory;
+use toml::{Table, Value};
+
+use crate::components::utils::BASE;
+
+use super::manager::library_file_name;
+
+pub const DEFAULT_MANIFEST_ICON: &str = "application-x-addon-symbolic";
+/// D-Bus types numbers can be written as, `d` is used if a row does not set one.
+pub const NUMBER_SIGNATURES: [&str; 5] = ["d", "i", "u", "x", "t"];
+
+/// A plugin described by a TOML or JSON file in the plugin directory,
+/// its rows are bound to D-Bus methods, properties and signals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginManifest {
+    pub name: String,
+    pub icon_name: String,
+    pub parent: Option<String>,
+    /// The plugin is only loaded if the daemon provides all of these capabilities.
+    pub capabilities: Vec<String>,
+    /// Terms the sidebar search finds the plugin by, in addition to its name and row titles.
+    pub keywords: Vec<String>,
+    pub rows: Vec<ManifestRow>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DBusTarget {
+    pub bus_name: String,
+    pub path: String,
+    pub interface: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RowKind {
+    Switch,
+    Slider { min: f64, max: f64, step: f64 },
+    Dropdown { options: Vec<String> },
+    Entry,
+    Button,
+    Label,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestRow {
+    pub kind: RowKind,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub target: DBusTarget,
+    /// Read with `org.freedesktop.DBus.Properties.Get` and written with `Set`.
+    pub property: Option<String>,
+    /// Method returning the value, used instead of the property.
+    pub getter: Option<String>,
+    /// Method taking the new value, used instead of the property.
+    pub setter: Option<String>,
+    /// Method without arguments called by buttons.
+    pub method: Option<String>,
+    /// Signal whose first argument is the new value.
+    pub signal: Option<String>,
+    /// D-Bus type numbers of sliders are written as.
+    pub signature: String,
+}
+
+impl PluginManifest {
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table = content
+            .parse::<Table>()
+            .map_err(|error| error.message().to_string())?;
+        Self::parse(&table)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let table = serde_json::from_str::<Table>(content).map_err(|error| error.to_string())?;
+        Self::parse(&table)
+    }
+
+    pub fn parse(table: &Table) -> Result<Self, String> {
+        let name = string(table, "name")?.ok_or("missing name")?;
+        if name.trim().is_empty() {
+            return Err(String::from("name is empty"));
+        }
+        let dbus = match table.get("dbus") {
+            Some(Value::Table(dbus)) => dbus.clone(),
+            Some(_) => return Err(String::from("dbus is not a table")),
+            None => Table::new(),
+        };
+        let default_target = (
+            string(&dbus, "bus_name")?.unwrap_or_else(|| String::from(BASE)),
+            string(&dbus, "path")?,
+            string(&dbus, "interface")?,
+        );
+        let rows = match table.get("rows") {
+            Some(Value::Array(rows)) => rows,
+            Some(_) => return Err(String::from("rows is not an array")),
+            None => return Err(String::from("missing rows")),
+        };
+        if rows.is_empty() {
+            return Err(String::from("rows is empty"));
+        }
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let row = row
+                    .as_table()
+                    .ok_or_else(|| format!("row {} is not a table", index + 1))?;
+                ManifestRow::parse(row, &default_target)
+                    .map_err(|error| format!("row {}: {}", index + 1, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            name,
+            icon_name: string(table, "icon")?
+                .unwrap_or_else(|| String::from(DEFAULT_MANIFEST_ICON)),
+            parent: string(table, "parent")?,
+            capabilities: string_list(table, "capabilities")?,
+            keywords: string_list(table, "keywords")?,
+            rows,
+        })
+    }
+}
+
+impl ManifestRow {
+    fn parse(
+        table: &Table,
+        default_target: &(String, Option<String>, Option<String>),
+    ) -> Result<Self, String> {
+        let kind = match string(table, "type")?.as_deref() {
+            Some("switch") => RowKind::Switch,
+            Some("slider") => {
+                let min = number(table, "min")?.unwrap_or(0.0);
+                let max = number(table, "max")?.unwrap_or(100.0);
+                let step = number(table, "step")?.unwrap_or(1.0);
+                if min >= max || step <= 0.0 {
+                    return Err(String::from("invalid slider range"));
+                }
+                RowKind::Slider { min, max, step }
+            }
+            Some("dropdown") => {
+                let options = string_list(table, "options")?;
+                if options.is_empty() {
+                    return Err(String::from("dropdown without options"));
+                }
+                RowKind::Dropdown { options }
+            }
+            Some("entry") => RowKind::Entry,
+            Some("button") => RowKind::Button,
+            Some("label") => RowKind::Label,
+            Some(kind) => return Err(format!("unknown row type {}", kind)),
+            None => return Err(String::from("missing type")),
+        };
+        let target = DBusTarget {
+            bus_name: string(table, "bus_name")?.unwrap_or_else(|| default_target.0.clone()),
+            path: string(table, "path")?
+                .or_else(|| default_target.1.clone())
+                .ok_or("missing path")?,
+            interface: string(table, "interface")?
+                .or_else(|| default_target.2.clone())
+                .ok_or("missing interface")?,
+        };
+        BusName::new(target.bus_name.as_str())
+            .map_err(|_| format!("invalid bus name {}", target.bus_name))?;
+        Path::new(target.path.as_str()).map_err(|_| format!("invalid path {}", target.path))?;
+        Interface::new(target.interface.as_str())
+            .map_err(|_| format!("invalid interface {}", target.interface))?;
+
+        let member = |entry: &str| -> Result<Option<String>, String> {
+            let name = string(table, entry)?;
+            if let Some(name) = &name {
+                Member::new(name.as_str()).map_err(|_| format!("invalid {} {}", entry, name))?;
+            }
+            Ok(name)
+        };
+        let row = Self {
+            title: string(table, "title")?.ok_or("missing title")?,
+            subtitle: string(table, "subtitle")?,
+            property: member("property")?,
+            getter: member("getter")?,
+            setter: member("setter")?,
+            method: member("method")?,
+            signal: member("signal")?,
+            signature: string(table, "signature")?.unwrap_or_else(|| String::from("d")),
+            kind,
+            target,
+        };
+        if !NUMBER_SIGNATURES.contains(&row.signature.as_str()) {
+            return Err(format!("unsupported signature {}", row.signature));
+        }
+        match row.kind {
+            RowKind::Button if row.method.is_none() => Err(String::from("button without method")),
+            RowKind::Label if row.property.is_none() && row.getter.is_none() => {
+                Err(String::from("label without property or getter"))
+            }
+            RowKind::Button | RowKind::Label => Ok(row),
+            _ if row.property.is_none() && row.getter.is_none() && row.setter.is_none() => {
+                Err(String::from("row without property, getter or setter"))
+            }
+            _ => Ok(row),
+        }
+    }
+
+    /// Whether the value of the row can be read from the daemon.
+    pub fn readable(&self) -> bool {
+        self.property.is_some() || self.getter.is_some()
+    }
+}
+
+fn string(table: &Table, entry: &str) -> Result<Option<String>, String> {
+    match table.get(entry) {
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(format!("{} is not a string", entry)),
+        None => Ok(None),
+    }
+}
+
+fn string_list(table: &Table, entry: &str) -> Result<Vec<String>, String> {
+    match table.get(entry) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .map(String::from)
+                    .ok_or_else(|| format!("{} contains a value that is not a string", entry))
+            })
+            .collect(),
+        Some(_) => Err(format!("{} is not an array", entry)),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn number(table: &Table, entry: &str) -> Result<Option<f64>, String> {
+    match table.get(entry) {
+        Some(Value::Float(value)) => Ok(Some(*value)),
+        Some(Value::Integer(value)) => Ok(Some(*value as f64)),
+        Some(_) => Err(format!("{} is not a number", entry)),
+        None => Ok(None),
+    }
+}
+
+/// Manifests are placed next to the plugin libraries.
+pub fn manifest_directory() -> Option<PathBuf> {
+    create_config_directory("reset").map(|directory| directory.join("plugins"))
+}
+
+/// Reads every manifest of the plugin directory, sorted by file name.
+pub fn read_manifests() -> Vec<(String, Result<PluginManifest, String>)> {
+    let entries = match manifest_directory().map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Vec::new(),
+    };
+    let mut manifests: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let parse: fn(&str) -> Result<PluginManifest, String> =
+                match path.extension().and_then(|extension| extension.to_str()) {
+                    Some("toml") => PluginManifest::from_toml,
+                    Some("json") => PluginManifest::from_json,
+                    _ => return None,
+                };
+            let manifest = fs::read_to_string(&path)
+                .map_err(|error| error.to_string())
+                .and_then(|content| parse(&content));
+            Some((library_file_name(&path), manifest))
+        })
+        .collect();
+    manifests.sort_by(|left, right| left.0.cmp(&right.0));
+    manifests
+}
@@ -0,0 +1,436 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use adw::prelude::{ActionRowExt, ComboRowExt, EntryRowExt, PreferencesGroupExt};
+use adw::{ActionRow, ComboRow, EntryRow, PreferencesGroup, SwitchRow};
+use dbus::arg::{ArgType, IterAppend, RefArg, Variant};
+use dbus::blocking::{BlockingSender, Connection};
+use dbus::message::{MatchRule, MessageType};
+use dbus::strings::{BusName, Interface, Member, Path};
+use dbus::Message;
+use glib::SendWeakRef;
+use gtk::prelude::*;
+use gtk::{gio, Orientation, Scale, StringList};
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+
+use crate::components::base::utils::Listeners;
+use crate::components::daemon::client::{connection, dbus_timeout};
+
+use super::manifest::{ManifestRow, PluginManifest, RowKind};
+
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManifestValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+/// A row of a manifest plugin together with the widget showing its value.
+pub struct ManifestRowWidget {
+    row: Arc<ManifestRow>,
+    widget: gtk::Widget,
+    // set while a value of the daemon is shown, which must not be written back
+    updating: Arc<AtomicBool>,
+}
+
+/// Builds the page of a manifest plugin, the values are only read once the page is shown.
+pub fn create_manifest_page(manifest: &PluginManifest) -> (gtk::Box, Vec<ManifestRowWidget>) {
+    let group = PreferencesGroup::builder().title(&manifest.name).build();
+    let rows: Vec<ManifestRowWidget> = manifest
+        .rows
+        .iter()
+        .map(|row| {
+            let (preferences_row, row_widget) = create_row(Arc::new(row.clone()));
+            group.add(&preferences_row);
+            row_widget
+        })
+        .collect();
+    let page = gtk::Box::new(Orientation::Vertical, 0);
+    page.append(&group);
+    (page, rows)
+}
+
+fn create_row(row: Arc<ManifestRow>) -> (gtk::Widget, ManifestRowWidget) {
+    let updating = Arc::new(AtomicBool::new(false));
+    let subtitle = row.subtitle.clone().unwrap_or_default();
+    let (preferences_row, widget): (gtk::Widget, gtk::Widget) = match &row.kind {
+        RowKind::Switch => {
+            let switch_row = SwitchRow::builder()
+                .title(&row.title)
+                .subtitle(&subtitle)
+                .build();
+            let (row, updating) = (row.clone(), updating.clone());
+            switch_row.connect_active_notify(move |switch_row| {
+                if !updating.load(Ordering::SeqCst) {
+                    write_value(row.clone(), ManifestValue::Bool(switch_row.is_active()));
+                }
+            });
+            (switch_row.clone().upcast(), switch_row.upcast())
+        }
+        RowKind::Slider { min, max, step } => {
+            let scale = Scale::with_range(Orientation::Horizontal, *min, *max, *step);
+            scale.set_hexpand(true);
+            let action_row = ActionRow::builder()
+                .title(&row.title)
+                .subtitle(&subtitle)
+                .build();
+            action_row.add_suffix(&scale);
+            let (row, updating) = (row.clone(), updating.clone());
+            let time_stamp = RefCell::new(None::<SystemTime>);
+            scale.connect_value_changed(move |scale| {
+                if updating.load(Ordering::SeqCst) {
+                    return;
+                }
+                // dragging the slider would otherwise flood the daemon with writes
+                {
+                    let mut time = time_stamp.borrow_mut();
+                    if time.is_some_and(|time| {
+                        time.elapsed().unwrap_or_default() < Duration::from_millis(50)
+                    }) {
+                        return;
+                    }
+                    *time = Some(SystemTime::now());
+                }
+                write_value(row.clone(), ManifestValue::Number(scale.value()));
+            });
+            (action_row.upcast(), scale.upcast())
+        }
+        RowKind::Dropdown { options } => {
+            let options: Vec<&str> = options.iter().map(String::as_str).collect();
+            let combo_row = ComboRow::builder()
+                .title(&row.title)
+                .subtitle(&subtitle)
+                .model(&StringList::new(&options))
+                .build();
+            let (row, updating) = (row.clone(), updating.clone());
+            combo_row.connect_selected_notify(move |combo_row| {
+                if updating.load(Ordering::SeqCst) {
+                    return;
+                }
+                if let RowKind::Dropdown { options } = &row.kind {
+                    if let Some(option) = options.get(combo_row.selected() as usize) {
+                        write_value(row.clone(), ManifestValue::Text(option.clone()));
+                    }
+                }
+            });
+            (combo_row.clone().upcast(), combo_row.upcast())
+        }
+        RowKind::Entry => {
+            let entry_row = EntryRow::builder()
+                .title(&row.title)
+                .show_apply_button(true)
+                .build();
+            let row = row.clone();
+            entry_row.connect_apply(move |entry_row| {
+                write_value(
+                    row.clone(),
+                    ManifestValue::Text(entry_row.text().to_string()),
+                );
+            });
+            (entry_row.clone().upcast(), entry_row.upcast())
+        }
+        RowKind::Button => {
+            let action_row = ActionRow::builder()
+                .title(&row.title)
+                .subtitle(&subtitle)
+                .activatable(true)
+                .build();
+            let row = row.clone();
+            action_row.connect_activated(move |_| {
+                let row = row.clone();
+                gio::spawn_blocking(move || {
+                    if let Err(_error) = call_method(&row) {
+                        ERROR!(
+                            format!("Could not call {}: {}", row.title, _error),
+                            ErrorLevel::Recoverable
+                        );
+                    }
+                });
+            });
+            (action_row.clone().upcast(), action_row.upcast())
+        }
+        RowKind::Label => {
+            let action_row = ActionRow::builder()
+                .title(&row.title)
+                .subtitle(&subtitle)
+                .use_markup(false)
+                .build();
+            (action_row.clone().upcast(), action_row.upcast())
+        }
+    };
+    (
+        preferences_row,
+        ManifestRowWidget {
+            row,
+            widget,
+            updating,
+        },
+    )
+}
+
+/// Reads the current values of all readable rows from the daemon.
+pub fn refresh_rows(rows: &[ManifestRowWidget]) {
+    for row_widget in rows.iter().filter(|row_widget| row_widget.row.readable()) {
+        let row = row_widget.row.clone();
+        let widget = row_widget.widget.clone();
+        let updating = row_widget.updating.clone();
+        glib::spawn_future_local(async move {
+            let _title = row.title.clone();
+            match gio::spawn_blocking(move || read_value(&row)).await {
+                Ok(Ok(value)) => show_value(&widget, &value, &updating),
+                Ok(Err(_error)) => ERROR!(
+                    format!("Could not read {}: {}", _title, _error),
+                    ErrorLevel::Recoverable
+                ),
+                Err(_) => (),
+            }
+        });
+    }
+}
+
+/// Updates the rows whenever their signal is emitted, until the page is left.
+pub fn listen_for_signals(listeners: &Listeners, rows: &[ManifestRowWidget]) {
+    let rows: Vec<_> = rows
+        .iter()
+        .filter(|row_widget| row_widget.row.signal.is_some())
+        .map(|row_widget| {
+            (
+                row_widget.row.clone(),
+                SendWeakRef::from(row_widget.widget.downgrade()),
+                row_widget.updating.clone(),
+            )
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+    let active = listeners.add_plugin_listener();
+    thread::spawn(move || {
+        let conn = match Connection::new_session() {
+            Ok(conn) => conn,
+            Err(_) => {
+                ERROR!(
+                    "Could not connect to the session bus for a manifest plugin",
+                    ErrorLevel::PartialBreakage
+                );
+                return;
+            }
+        };
+        for (row, widget, updating) in rows {
+            let rule = match signal_rule(&row) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let res = conn.add_match(rule, move |_: (), _, message| {
+                let value = message
+                    .iter_init()
+                    .get_refarg()
+                    .and_then(|argument| value_from_arg(&row.kind, &*argument));
+                if let Some(value) = value {
+                    let (widget, updating) = (widget.clone(), updating.clone());
+                    glib::MainContext::default().invoke(move || {
+                        if let Some(widget) = widget.upgrade() {
+                            show_value(&widget, &value, &updating);
+                        }
+                    });
+                }
+                true
+            });
+            if res.is_err() {
+                ERROR!(
+                    "Could not listen to a signal of a manifest plugin",
+                    ErrorLevel::Recoverable
+                );
+            }
+        }
+        while active.load(Ordering::SeqCst) {
+            let _ = conn.process(Duration::from_millis(1000));
+        }
+    });
+}
+
+fn signal_rule(row: &ManifestRow) -> Option<MatchRule<'static>> {
+    let mut rule = MatchRule::new();
+    rule.msg_type = Some(MessageType::Signal);
+    rule.sender = Some(BusName::new(row.target.bus_name.clone()).ok()?);
+    rule.path = Some(Path::new(row.target.path.clone()).ok()?);
+    rule.interface = Some(Interface::new(row.target.interface.clone()).ok()?);
+    rule.member = Some(Member::new(row.signal.clone()?).ok()?);
+    Some(rule)
+}
+
+fn show_value(widget: &gtk::Widget, value: &ManifestValue, updating: &AtomicBool) {
+    updating.store(true, Ordering::SeqCst);
+    if let (Some(switch_row), ManifestValue::Bool(active)) =
+        (widget.downcast_ref::<SwitchRow>(), value)
+    {
+        switch_row.set_active(*active);
+    } else if let (Some(scale), ManifestValue::Number(number)) =
+        (widget.downcast_ref::<Scale>(), value)
+    {
+        scale.set_value(*number);
+    } else if let Some(combo_row) = widget.downcast_ref::<ComboRow>() {
+        let text = value_text(value);
+        let model = combo_row.model().and_downcast::<StringList>();
+        let position = model.and_then(|model| {
+            (0..model.n_items()).find(|index| {
+                model
+                    .string(*index)
+                    .is_some_and(|item| item.as_str() == text)
+            })
+        });
+        if let Some(position) = position {
+            combo_row.set_selected(position);
+        }
+    } else if let Some(entry_row) = widget.downcast_ref::<EntryRow>() {
+        entry_row.set_text(&value_text(value));
+    } else if let Some(action_row) = widget.downcast_ref::<ActionRow>() {
+        action_row.set_subtitle(&value_text(value));
+    }
+    updating.store(false, Ordering::SeqCst);
+}
+
+/// Converts a value sent by the daemon to the value of a row.
+pub fn value_from_arg(kind: &RowKind, argument: &dyn RefArg) -> Option<ManifestValue> {
+    match kind {
+        RowKind::Switch => argument
+            .as_u64()
+            .map(|value| ManifestValue::Bool(value != 0)),
+        RowKind::Slider { .. } => argument
+            .as_f64()
+            .or_else(|| argument.as_i64().map(|value| value as f64))
+            .or_else(|| argument.as_u64().map(|value| value as f64))
+            .map(ManifestValue::Number),
+        _ => Some(ManifestValue::Text(arg_text(argument))),
+    }
+}
+
+pub fn arg_text(argument: &dyn RefArg) -> String {
+    if let Some(text) = argument.as_str() {
+        return text.to_string();
+    }
+    if argument.arg_type() == ArgType::Boolean {
+        return (argument.as_u64() != Some(0)).to_string();
+    }
+    if let Some(value) = argument.as_i64() {
+        return value.to_string();
+    }
+    if let Some(value) = argument.as_u64() {
+        return value.to_string();
+    }
+    if let Some(value) = argument.as_f64() {
+        return value.to_string();
+    }
+    if argument.arg_type() == ArgType::Variant {
+        if let Some(inner) = argument.as_iter().and_then(|mut inner| inner.next()) {
+            return arg_text(inner);
+        }
+    }
+    format!("{:?}", argument)
+}
+
+fn value_text(value: &ManifestValue) -> String {
+    match value {
+        ManifestValue::Bool(value) => value.to_string(),
+        ManifestValue::Number(value) => value.to_string(),
+        ManifestValue::Text(value) => value.clone(),
+    }
+}
+
+/// Converts the value of a row to the argument written to the daemon.
+pub fn value_arg(value: &ManifestValue, signature: &str) -> Box<dyn RefArg> {
+    match value {
+        ManifestValue::Bool(value) => Box::new(*value),
+        ManifestValue::Number(value) => match signature {
+            "i" => Box::new(value.round() as i32),
+            "u" => Box::new(value.round().max(0.0) as u32),
+            "x" => Box::new(value.round() as i64),
+            "t" => Box::new(value.round().max(0.0) as u64),
+            _ => Box::new(*value),
+        },
+        ManifestValue::Text(value) => Box::new(value.clone()),
+    }
+}
+
+fn method_call(row: &ManifestRow, interface: &str, method: &str) -> Result<Message, String> {
+    Message::new_method_call(
+        row.target.bus_name.as_str(),
+        row.target.path.as_str(),
+        interface,
+        method,
+    )
+}
+
+fn send(message: Message) -> Result<Message, String> {
+    let conn = connection().map_err(|error| error.to_string())?;
+    conn.send_with_reply_and_block(message, dbus_timeout())
+        .map_err(|error| error.to_string())
+}
+
+fn read_value(row: &ManifestRow) -> Result<ManifestValue, String> {
+    if let Some(property) = &row.property {
+        let message = method_call(row, PROPERTIES_INTERFACE, "Get")?
+            .append2(row.target.interface.as_str(), property.as_str());
+        let reply = send(message)?;
+        let value: Variant<Box<dyn RefArg>> = reply.read1().map_err(|error| error.to_string())?;
+        return value_from_arg(&row.kind, &*value.0)
+            .ok_or_else(|| format!("unexpected type of {}", property));
+    }
+    let getter = row.getter.as_deref().ok_or("no property or getter")?;
+    let reply = send(method_call(row, &row.target.interface, getter)?)?;
+    let arguments: Vec<Box<dyn RefArg>> = reply.iter_init().collect();
+    if let (RowKind::Label, true) = (&row.kind, arguments.len() > 1) {
+        // labels show every value returned by the getter
+        let texts: Vec<String> = arguments
+            .iter()
+            .map(|argument| arg_text(&**argument))
+            .collect();
+        return Ok(ManifestValue::Text(texts.join(", ")));
+    }
+    arguments
+        .first()
+        .and_then(|argument| value_from_arg(&row.kind, &**argument))
+        .ok_or_else(|| format!("unexpected reply of {}", getter))
+}
+
+fn write_value(row: Arc<ManifestRow>, value: ManifestValue) {
+    gio::spawn_blocking(move || {
+        let argument = value_arg(&value, &row.signature);
+        let message = match (&row.property, &row.setter) {
+            (_, Some(setter)) => {
+                method_call(&row, &row.target.interface, setter).map(|mut message| {
+                    argument.append(&mut IterAppend::new(&mut message));
+                    message
+                })
+            }
+            (Some(property), None) => {
+                method_call(&row, PROPERTIES_INTERFACE, "Set").map(|message| {
+                    message.append3(
+                        row.target.interface.as_str(),
+                        property.as_str(),
+                        Variant(argument),
+                    )
+                })
+            }
+            (None, None) => return,
+        };
+        if let Err(_error) = message.and_then(send) {
+            ERROR!(
+                format!("Could not write {}: {}", row.title, _error),
+                ErrorLevel::Recoverable
+            );
+        }
+    });
+}
+
+fn call_method(row: &ManifestRow) -> Result<(), String> {
+    let method = row.method.as_deref().ok_or("no method")?;
+    send(method_call(row, &row.target.interface, method)?).map(|_| ())
+}
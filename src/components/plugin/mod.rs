@@ -1 +1,10 @@
+pub mod abi;
+pub mod extension;
 pub mod function;
+pub mod host;
+pub mod manager;
+pub mod manifest;
+pub mod manifest_page;
+pub mod plugin_page;
+pub mod reload;
+pub mod search;
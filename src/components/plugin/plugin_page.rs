@@ -0,0 +1,90 @@
+use adw::prelude::{ActionRowExt, PreferencesGroupExt};
+use adw::{ActionRow, PreferencesGroup};
+use glib::Propagation;
+use gtk::prelude::*;
+use gtk::{Align, Switch};
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+
+use crate::components::config::frontend::set_plugin_enabled;
+
+use super::manager::{plugin_infos, PluginInfo, PluginStatus};
+
+pub fn create_plugin_page() -> PreferencesGroup {
+    let group = PreferencesGroup::builder()
+        .title("Plugins")
+        .description(
+            "Disabled plugins are not started on the next launch. \
+             Their library is still loaded unless it is removed from plugins in ReSet.toml.",
+        )
+        .build();
+    let plugins = plugin_infos();
+    if plugins.is_empty() {
+        group.add(&ActionRow::builder().title("No plugins installed").build());
+    }
+    for plugin in plugins {
+        group.add(&create_plugin_row(plugin));
+    }
+    group
+}
+
+fn create_plugin_row(plugin: PluginInfo) -> ActionRow {
+    let row = ActionRow::builder()
+        .title(plugin.display_name())
+        .subtitle(plugin_description(&plugin))
+        .use_markup(false)
+        .build();
+    let switch = Switch::builder()
+        .active(!matches!(
+            plugin.status,
+            PluginStatus::Disabled | PluginStatus::Failed(_) | PluginStatus::Incompatible(_)
+        ))
+        .valign(Align::Center)
+        // failed and incompatible plugins are only retried after a restart
+        .sensitive(!matches!(
+            plugin.status,
+            PluginStatus::Failed(_) | PluginStatus::Incompatible(_)
+        ))
+        .build();
+    // the config watcher applies the change and rebuilds this page
+    switch.connect_state_set(move |_, enabled| {
+        let mut identifiers = vec![plugin.file_name.as_str()];
+        if let Some(name) = &plugin.name {
+            identifiers.push(name);
+        }
+        if let Err(_error) = set_plugin_enabled(&identifiers, enabled) {
+            ERROR!(_error, ErrorLevel::Recoverable);
+            return Propagation::Stop;
+        }
+        Propagation::Proceed
+    });
+    row.add_suffix(&switch);
+    row.set_activatable_widget(Some(&switch));
+    row
+}
+
+fn plugin_description(plugin: &PluginInfo) -> String {
+    let kind = plugin.kind.map_or("unknown", |kind| kind.label());
+    let capabilities = if plugin.capabilities.is_empty() {
+        String::from("none")
+    } else {
+        plugin.capabilities.join(", ")
+    };
+    let requirement = match (plugin.requires_capabilities, plugin.capabilities_satisfied) {
+        (false, _) => "optional",
+        (true, true) => "provided by the daemon",
+        (true, false) => "not provided by the daemon",
+    };
+    let status = match &plugin.status {
+        PluginStatus::Loaded => String::from("Loaded"),
+        PluginStatus::Disabled => String::from("Disabled"),
+        PluginStatus::MissingCapabilities => String::from("Not loaded, missing capabilities"),
+        PluginStatus::Failed(error) => format!("Disabled until the next start, {}", error),
+        PluginStatus::Incompatible(message) => format!("Incompatible, {}", message),
+    };
+    format!(
+        "{}\nImplementation: {}\nCapabilities: {} ({})\n{}",
+        plugin.file_name, kind, capabilities, requirement, status
+    )
+}
@@ -0,0 +1,202 @@
+use std::cell::Cell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use gtk::gio;
+use gtk::gio::prelude::{FileExt, FileMonitorExt};
+use libloading::{Library, Symbol};
+use re_set_lib::utils::plugin::{PluginCapabilities, PluginTestFunc, SidebarInfo};
+use re_set_lib::utils::plugin_setup::{FrontendPluginFunctions, FRONTEND_PLUGINS};
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
+use reset_plugin_api::entry::PanicTaker;
+
+use crate::cli::plugin_tests::CHECK_PLUGIN_ABI_FLAG;
+
+use super::manager::{call_plugin, library_file_name, plugin_library};
+
+// compilers and linkers write libraries in several steps
+const RELOAD_DELAY: Duration = Duration::from_millis(500);
+
+static RELOAD_COUNT: AtomicUsize = AtomicUsize::new(0);
+/// File names of reloaded plugins with the path of the library they were built to.
+static RELOADED_PLUGINS: Mutex<Vec<(String, PathBuf)>> = Mutex::new(Vec::new());
+
+/// Reloaded libraries are loaded from copies, as loading the same path again
+/// would only return the library that is already loaded.
+pub fn reload_directory() -> PathBuf {
+    std::env::temp_dir().join(format!("reset-plugins-{}", std::process::id()))
+}
+
+/// Removes the copies of reloaded libraries, called with [`reload_directory`] when ReSet exits.
+/// The libraries themselves stay mapped until then, removing their files does not unload them.
+pub fn remove_reload_copies(directory: &Path) {
+    if !directory.exists() {
+        return;
+    }
+    if let Err(_error) = fs::remove_dir_all(directory) {
+        ERROR!(
+            format!("Could not remove reloaded plugins: {}", _error),
+            ErrorLevel::Recoverable
+        );
+    }
+}
+
+/// The file names of the loaded frontend plugins with the path of their library.
+pub fn plugin_sources() -> Vec<(String, PathBuf)> {
+    let reloaded = RELOADED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mut sources = Vec::new();
+    unsafe {
+        for plugin in FRONTEND_PLUGINS.iter() {
+            let path = match plugin_library(plugin) {
+                Some(path) => path,
+                None => continue,
+            };
+            let file = library_file_name(&path);
+            let source = plugin_source(&file, path, &reloaded);
+            sources.push((file, source));
+        }
+    }
+    sources
+}
+
+/// Reloaded plugins are loaded from a copy, their source is the path the new build was read from.
+pub fn plugin_source(file: &str, path: PathBuf, reloaded: &[(String, PathBuf)]) -> PathBuf {
+    reloaded
+        .iter()
+        .find(|(reloaded_file, _)| reloaded_file == file)
+        .map_or(path, |(_, source)| source.clone())
+}
+
+/// Checks the ABI of a new build in a separate ReSet process, as loading it runs its constructors,
+/// which an incompatible build must not do in this one.
+fn check_new_build(copy: &Path) -> Result<(), String> {
+    let output = std::env::current_exe()
+        .and_then(|executable| {
+            Command::new(executable)
+                .arg(CHECK_PLUGIN_ABI_FLAG)
+                .arg(copy)
+                .output()
+        })
+        .map_err(|error| format!("could not check the new build: {}", error))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+}
+
+/// Loads a new build of a frontend plugin in place of the old one in `plugins`,
+/// which has to be shut down.
+/// The old library stays loaded, and its copy on disk until ReSet exits,
+/// as widgets and types it registered may still be in use.
+pub fn replace_plugin(
+    plugins: &mut [FrontendPluginFunctions],
+    plugin_file: &str,
+    source: &Path,
+) -> Result<(), String> {
+    let index = plugins
+        .iter()
+        .position(|plugin| {
+            plugin_library(plugin)
+                .as_deref()
+                .map(library_file_name)
+                .is_some_and(|file| file == plugin_file)
+        })
+        .ok_or_else(|| format!("{} is not a loaded frontend plugin", plugin_file))?;
+
+    let directory =
+        reload_directory().join(RELOAD_COUNT.fetch_add(1, Ordering::SeqCst).to_string());
+    let copy = directory.join(plugin_file);
+    fs::create_dir_all(&directory)
+        .and_then(|_| fs::copy(source, &copy))
+        .map_err(|error| format!("could not copy the new build: {}", error))?;
+    if let Err(error) = check_new_build(&copy) {
+        // the copy was never loaded
+        let _ = fs::remove_dir_all(&directory);
+        return Err(error);
+    }
+    let library = unsafe { Library::new(&copy) }
+        .map_err(|error| format!("could not load the new build: {}", error))?;
+    // symbols of plugins live as long as ReSet, like the libraries loaded by re_set-lib
+    let library: &'static Library = Box::leak(Box::new(library));
+    plugins[index] = unsafe { frontend_functions(library) }?;
+
+    let mut reloaded = RELOADED_PLUGINS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    reloaded.retain(|(file, _)| file != plugin_file);
+    reloaded.push((plugin_file.to_string(), source.to_path_buf()));
+    Ok(())
+}
+
+unsafe fn frontend_functions(library: &'static Library) -> Result<FrontendPluginFunctions, String> {
+    fn symbol<T>(library: &'static Library, name: &str) -> Result<Symbol<'static, T>, String> {
+        unsafe { library.get::<T>(name.as_bytes()) }
+            .map_err(|_| format!("the new build does not export {}", name))
+    }
+    let capabilities =
+        symbol::<unsafe extern "C" fn() -> PluginCapabilities>(library, "capabilities")?;
+    let take_panic = symbol::<PanicTaker>(library, "frontend_take_panic")?;
+    let capabilities = call_plugin("capabilities", Some(*take_panic), || capabilities())
+        .map_err(|error| error.to_string())?
+        .get_capabilities();
+    Ok(FrontendPluginFunctions::new(
+        capabilities,
+        symbol::<unsafe extern "C" fn() -> String>(library, "frontend_name")?,
+        symbol::<unsafe extern "C" fn()>(library, "frontend_startup")?,
+        symbol::<unsafe extern "C" fn()>(library, "frontend_shutdown")?,
+        symbol::<unsafe extern "C" fn() -> (SidebarInfo, Vec<gtk::Box>)>(library, "frontend_data")?,
+        symbol::<unsafe extern "C" fn() -> Vec<PluginTestFunc>>(library, "frontend_tests")?,
+    ))
+}
+
+/// Calls `on_change` with the file name of a plugin and the path of its library
+/// once the library was rebuilt.
+/// The returned monitors have to be kept alive for as long as changes should be reported.
+pub fn watch_plugin_libraries<F: Fn(&str, &Path) + 'static>(on_change: F) -> Vec<gio::FileMonitor> {
+    let on_change = Rc::new(on_change);
+    let mut monitors = Vec::new();
+    for (plugin_file, source) in plugin_sources() {
+        // plugins linked into the plugin directory are rebuilt at the target of the link
+        let watched = fs::canonicalize(&source).unwrap_or_else(|_| source.clone());
+        let monitor = gio::File::for_path(&watched)
+            .monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE);
+        let monitor = match monitor {
+            Ok(monitor) => monitor,
+            Err(_error) => {
+                ERROR!(
+                    format!("Could not watch plugin {}: {}", plugin_file, _error),
+                    ErrorLevel::Recoverable
+                );
+                continue;
+            }
+        };
+        let pending = Rc::new(Cell::new(false));
+        let on_change = on_change.clone();
+        monitor.connect_changed(move |_, _, _, event| {
+            if !matches!(
+                event,
+                gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created
+            ) || pending.replace(true)
+            {
+                return;
+            }
+            let (pending, on_change) = (pending.clone(), on_change.clone());
+            let (plugin_file, source) = (plugin_file.clone(), source.clone());
+            glib::timeout_add_local_once(RELOAD_DELAY, move || {
+                pending.set(false);
+                on_change(&plugin_file, &source);
+            });
+        });
+        monitors.push(monitor);
+    }
+    monitors
+}
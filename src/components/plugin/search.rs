@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use super::manager::{call_plugin, open_loaded_library, panic_taker, PluginError};
+
+/// Plugins may export `fn frontend_search_keywords() -> Vec<SearchKeyword>`
+/// to be found by the sidebar search with more than the name of their page.
+pub const PLUGIN_SEARCH_SYMBOL: &[u8] = b"frontend_search_keywords";
+
+/// A term the sidebar search finds a page by, plugins declare an identical struct.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchKeyword {
+    pub keyword: String,
+    /// Title of the row that is highlighted after opening the page through this keyword.
+    pub row: Option<String>,
+}
+
+impl SearchKeyword {
+    pub fn new(keyword: &str, row: Option<&str>) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            row: row.map(String::from),
+        }
+    }
+
+    /// Finds a row by its title.
+    pub fn row(title: &str) -> Self {
+        Self::new(title, Some(title))
+    }
+}
+
+/// Asks a started plugin for its keywords, plugins without `frontend_search_keywords` have none.
+pub fn plugin_search_keywords(path: &Path) -> Result<Vec<SearchKeyword>, PluginError> {
+    let library = match open_loaded_library(path) {
+        Some(library) => library,
+        None => return Ok(Vec::new()),
+    };
+    unsafe {
+        match library.get::<unsafe extern "C" fn() -> Vec<SearchKeyword>>(PLUGIN_SEARCH_SYMBOL) {
+            Ok(frontend_search_keywords) => {
+                call_plugin("frontend_search_keywords", panic_taker(path), || {
+                    frontend_search_keywords()
+                })
+            }
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+}
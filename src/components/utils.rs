@@ -1,11 +1,8 @@
 use std::cell::Cell;
-use std::time::Duration;
 
 use adw::gdk::pango::EllipsizeMode;
 use adw::prelude::ListModelExtManual;
 use adw::{ActionRow, ComboRow};
-use dbus::blocking::Connection;
-use dbus::Error;
 use glib::prelude::Cast;
 use glib::Object;
 use gtk::prelude::{GObjectPropertyExpressionExt, ListBoxRowExt, ListItemExt, WidgetExt};
@@ -14,6 +11,8 @@ use re_set_lib::ERROR;
 #[cfg(debug_assertions)]
 use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
 
+use crate::components::daemon::proxy::{base::BaseProxy, DaemonClient};
+
 pub const DBUS_PATH: &str = "/org/Xetibo/ReSet/Daemon";
 pub const WIRELESS: &str = "org.Xetibo.ReSet.Network";
 pub const BLUETOOTH: &str = "org.Xetibo.ReSet.Bluetooth";
@@ -93,9 +92,7 @@ pub fn set_action_row_ellipsis(element: ActionRow) {
 }
 
 pub fn get_capabilities() -> Vec<String> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(10000));
-    let res: Result<(Vec<String>,), Error> = proxy.method_call(BASE, "GetCapabilities", ());
+    let res = DaemonClient::long().get_capabilities();
     if res.is_err() {
         ERROR!(
             "Could not call capabilities from daemon",
@@ -103,5 +100,5 @@ pub fn get_capabilities() -> Vec<String> {
         );
         return Vec::new();
     }
-    res.unwrap().0
+    res.unwrap()
 }
@@ -1,19 +1,20 @@
 use std::rc::Rc;
-use std::time::Duration;
 
-use crate::components::utils::{BASE, DBUS_PATH, WIRELESS};
+use crate::components::daemon::proxy::network::NetworkProxy;
+use crate::components::daemon::proxy::DaemonClient;
 use crate::components::wifi::saved_wifi_entry_impl;
 use crate::components::wifi::utils::get_connection_settings;
 use crate::components::wifi::wifi_box_impl::WifiBox;
 use crate::components::wifi::wifi_options::WifiOptions;
+use crate::components::window::reset_window::show_undo_toast;
 use adw::glib::Object;
 use adw::prelude::{ActionRowExt, ButtonExt, PreferencesGroupExt, PreferencesRowExt};
-use dbus::blocking::Connection;
-use dbus::{Error, Path};
+use dbus::Path;
 use glib::clone;
 use glib::property::PropertySet;
 use glib::subclass::types::ObjectSubclassIsExt;
-use gtk::prelude::{BoxExt, ListBoxRowExt};
+use gtk::gio::prelude::ApplicationExtManual;
+use gtk::prelude::{BoxExt, ListBoxRowExt, WidgetExt};
 use gtk::{gio, Align, Button, Orientation};
 
 glib::wrapper! {
@@ -53,20 +54,35 @@ impl SavedWifiEntry {
         );
 
         let entry_ref = entry.clone();
-        delete_button.connect_clicked(clone!(@weak wifi_box => move |_| {
-            delete_connection(entry_ref.imp().reset_connection_path.take());
-            // FUTURE TODO: handle error
-            wifi_box.reset_stored_wifi_list.remove(&*entry_ref);
-
+        delete_button.connect_clicked(clone!(@weak wifi_box => move |button| {
+            let entry = entry_ref.clone();
+            entry.set_visible(false);
+            show_undo_toast(
+                button,
+                &format!("Deleted {}", entry.title()),
+                clone!(@strong entry => move || entry.set_visible(true)),
+                move || {
+                    let path = entry.imp().reset_connection_path.borrow().clone();
+                    // keeps ReSet running until the daemon answered, the window may be closing
+                    let hold = gio::Application::default().map(|app| app.hold());
+                    glib::spawn_future_local(async move {
+                        // the entry is shown again if the connection could not be deleted, the error is logged
+                        if delete_connection(path).await {
+                            wifi_box.reset_stored_wifi_list.remove(&*entry);
+                        } else {
+                            entry.set_visible(true);
+                        }
+                        drop(hold);
+                    });
+                },
+            );
         }));
         entry
     }
 }
 
-fn delete_connection(path: Path<'static>) {
-    gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let _: Result<(), Error> = proxy.method_call(WIRELESS, "DeleteConnection", (path,));
-    });
+/// Returns whether the daemon deleted the connection, failures are logged by the client.
+async fn delete_connection(path: Path<'static>) -> bool {
+    let result = gio::spawn_blocking(move || DaemonClient::new().delete_connection(path)).await;
+    matches!(result, Ok(Ok(true)))
 }
@@ -6,13 +6,15 @@ use std::time::Duration;
 
 use crate::components::base::error_impl::{show_error, ReSetErrorImpl};
 use crate::components::base::utils::Listeners;
-use crate::components::utils::{set_combo_row_ellipsis, BASE, DBUS_PATH, WIRELESS};
+use crate::components::daemon::proxy::network::{NetworkProxy, StoredConnection};
+use crate::components::daemon::proxy::DaemonClient;
+use crate::components::plugin::extension::{insert_extensions, ExtensionPoint};
+use crate::components::utils::{set_combo_row_ellipsis, BASE, DBUS_PATH};
 use adw::glib::Object;
 use adw::prelude::{ComboRowExt, ListBoxRowExt, PreferencesGroupExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
 use dbus::blocking::Connection;
 use dbus::message::SignalArgs;
-use dbus::Error;
 use dbus::Path;
 use glib::prelude::Cast;
 use glib::property::PropertySet;
@@ -45,8 +47,6 @@ glib::wrapper! {
     @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Orientable;
 }
 
-type ResultMap = Result<(Vec<(Path<'static>, Vec<u8>)>,), Error>;
-
 unsafe impl Send for WifiBox {}
 unsafe impl Sync for WifiBox {}
 
@@ -60,6 +60,13 @@ impl ReSetErrorImpl for WifiBox {
 
 impl WifiBox {
     pub fn new(listeners: Arc<Listeners>) -> Arc<Self> {
+        let obj = Self::without_extensions(listeners);
+        insert_extensions(ExtensionPoint::WifiBox, &obj.imp().reset_wifi_main_page);
+        obj
+    }
+
+    /// For the quick settings, plugin widgets stay in the main window.
+    pub fn without_extensions(listeners: Arc<Listeners>) -> Arc<Self> {
         let obj: Arc<WifiBox> = Arc::new(Object::builder().build());
         setup_callbacks(listeners, obj)
     }
@@ -98,7 +105,8 @@ fn setup_callbacks(listeners: Arc<Listeners>, wifi_box: Arc<WifiBox>) -> Arc<Wif
                 }
                 map.clear();
                 imp.wifi_entries_path.write().unwrap().clear();
-                listeners.wifi_listener.store(false, Ordering::SeqCst);
+                // the listener of the daemon is kept for when WiFi is enabled again
+                listeners.release_network_listener();
             } else {
                 start_event_listener(listeners.clone(), wifibox_ref.clone());
                 show_stored_connections(wifibox_ref.clone());
@@ -221,76 +229,57 @@ pub fn show_stored_connections(wifi_box: Arc<WifiBox>) {
 }
 
 pub fn dbus_start_network_events(wifi_box: Arc<WifiBox>) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(), Error> = proxy.method_call(WIRELESS, "StartNetworkListener", ());
+    let res = DaemonClient::new().start_network_listener();
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to start Network listener");
     }
 }
 
 pub fn get_access_points(wifi_box: Arc<WifiBox>) -> Vec<AccessPoint> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(Vec<AccessPoint>,), Error> =
-        proxy.method_call(WIRELESS, "ListAccessPoints", ());
+    let res = DaemonClient::new().list_access_points();
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to list access points");
         return Vec::new();
     }
-    let (access_points,) = res.unwrap();
-    access_points
+    res.unwrap()
 }
 
 pub fn set_wifi_device(path: Path<'static>, wifi_box: Arc<WifiBox>) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(bool,), Error> = proxy.method_call(WIRELESS, "SetWifiDevice", (path,));
+    let res = DaemonClient::new().set_wifi_device(path);
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to set WiFi devices");
     }
 }
 
 pub fn get_wifi_devices(wifi_box: Arc<WifiBox>) -> Vec<WifiDevice> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(Vec<WifiDevice>,), Error> =
-        proxy.method_call(WIRELESS, "GetAllWifiDevices", ());
+    let res = DaemonClient::new().get_all_wifi_devices();
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to get WiFi devices");
         return Vec::new();
     }
-    let (devices,) = res.unwrap();
-    devices
+    res.unwrap()
 }
 
 pub fn get_wifi_status(wifi_box: Arc<WifiBox>) -> bool {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(bool,), Error> = proxy.method_call(WIRELESS, "GetWifiStatus", ());
+    let res = DaemonClient::new().get_wifi_status();
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to get WiFi status");
         return false;
     }
-    res.unwrap().0
+    res.unwrap()
 }
 
-pub fn get_stored_connections(wifi_box: Arc<WifiBox>) -> Vec<(Path<'static>, Vec<u8>)> {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: ResultMap = proxy.method_call(WIRELESS, "ListStoredConnections", ());
+pub fn get_stored_connections(wifi_box: Arc<WifiBox>) -> Vec<StoredConnection> {
+    let res = DaemonClient::new().list_stored_connections();
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to list stored connections");
         return Vec::new();
     }
-    let (connections,) = res.unwrap();
-    connections
+    res.unwrap()
 }
 
 pub fn set_wifi_enabled(enabled: bool, wifi_box: Arc<WifiBox>) {
-    let conn = Connection::new_session().unwrap();
-    let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-    let res: Result<(bool,), Error> = proxy.method_call(WIRELESS, "SetWifiEnabled", (enabled,));
+    let res = DaemonClient::new().set_wifi_enabled(enabled);
     if res.is_err() {
         show_error::<WifiBox>(wifi_box.clone(), "Failed to enable WiFi");
     }
@@ -298,14 +287,18 @@ pub fn set_wifi_enabled(enabled: bool, wifi_box: Arc<WifiBox>) {
 
 pub fn start_event_listener(listeners: Arc<Listeners>, wifi_box: Arc<WifiBox>) {
     gio::spawn_blocking(move || {
-        if listeners.wifi_disabled.load(Ordering::SeqCst)
-            || listeners.wifi_listener.load(Ordering::SeqCst)
-        {
+        if listeners.wifi_disabled.load(Ordering::SeqCst) || !listeners.acquire_network_listener() {
             return;
         }
-        listeners.wifi_listener.store(true, Ordering::SeqCst);
 
-        let conn = Connection::new_session().unwrap();
+        let conn = match Connection::new_session() {
+            Ok(conn) => conn,
+            Err(_) => {
+                listeners.release_network_listener();
+                show_error::<WifiBox>(wifi_box.clone(), "Failed to start Network listener");
+                return;
+            }
+        };
         let added_ref = wifi_box.clone();
         let removed_ref = wifi_box.clone();
         let changed_ref = wifi_box.clone();
@@ -20,6 +20,8 @@ pub struct WifiBox {
     #[template_child]
     pub reset_wifi_navigation: TemplateChild<NavigationView>,
     #[template_child]
+    pub reset_wifi_main_page: TemplateChild<gtk::Box>,
+    #[template_child]
     pub reset_wifi_details: TemplateChild<PreferencesGroup>,
     #[template_child]
     pub reset_wifi_device: TemplateChild<ComboRow>,
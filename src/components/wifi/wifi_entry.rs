@@ -1,14 +1,11 @@
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
 
-use crate::components::utils::{BASE, DBUS_PATH, WIRELESS};
+use crate::components::daemon::proxy::{network::NetworkProxy, DaemonClient};
 use crate::components::wifi::utils::get_connection_settings;
 use adw::glib::Object;
 use adw::prelude::{ActionRowExt, ButtonExt, EditableExt, PopoverExt, PreferencesRowExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use dbus::blocking::Connection;
-use dbus::Error;
 use glib::clone;
 use glib::property::PropertySet;
 use gtk::prelude::{BoxExt, ListBoxRowExt, WidgetExt};
@@ -127,10 +124,7 @@ pub fn click_disconnect(entry: Arc<WifiEntry>) {
     entry.set_activatable(false);
     gio::spawn_blocking(move || {
         let imp = entry_ref.imp();
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(10000));
-        let res: Result<(bool,), Error> =
-            proxy.method_call(WIRELESS, "DisconnectFromCurrentAccessPoint", ());
+        let res = DaemonClient::long().disconnect_from_current_access_point();
         if res.is_err() {
             imp.connected.replace(false);
             return;
@@ -155,10 +149,7 @@ pub fn click_stored_network(entry: Arc<WifiEntry>) {
         .borrow()
         .set_text("Connecting...");
     gio::spawn_blocking(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(10000));
-        let res: Result<(bool,), Error> =
-            proxy.method_call(WIRELESS, "ConnectToKnownAccessPoint", (access_point,));
+        let res = DaemonClient::long().connect_to_known_access_point(access_point);
         glib::spawn_future(async move {
             glib::idle_add_once(move || {
                 entry.set_sensitive(true);
@@ -167,7 +158,7 @@ pub fn click_stored_network(entry: Arc<WifiEntry>) {
                     imp.connected.replace(false);
                     return;
                 }
-                if res.unwrap() == (false,) {
+                if !res.unwrap() {
                     imp.connected.replace(false);
                     return;
                 }
@@ -192,13 +183,7 @@ pub fn click_new_network(entry: Arc<WifiEntry>) {
             entry.set_sensitive(false);
 
             gio::spawn_blocking(move || {
-                let conn = Connection::new_session().unwrap();
-                let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(10000));
-                let res: Result<(bool,), Error> = proxy.method_call(
-                    WIRELESS,
-                    "ConnectToNewAccessPoint",
-                    (access_point, password),
-                );
+                let res = DaemonClient::long().connect_to_new_access_point(access_point, password);
                 glib::spawn_future(async move {
                     glib::idle_add_once(move || {
                         entry.set_sensitive(false);
@@ -211,7 +196,7 @@ pub fn click_new_network(entry: Arc<WifiEntry>) {
                             imp.connected.replace(false);
                             return;
                         }
-                        if res.unwrap() == (false,) {
+                        if !res.unwrap() {
                             let imp = entry_ref.imp();
                             imp.reset_wifi_popup
                                 .imp()
@@ -2,14 +2,13 @@ use std::collections::HashMap;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
 
 use adw::gio;
 use adw::glib::Object;
 use adw::prelude::{ActionRowExt, ComboRowExt, PreferencesGroupExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
 use dbus::arg::PropMap;
-use dbus::{Error, Path};
+use dbus::Path;
 use glib::clone;
 use glib::property::PropertySet;
 use gtk::prelude::{ActionableExt, ButtonExt, EditableExt, ListBoxRowExt, WidgetExt};
@@ -19,7 +18,7 @@ use re_set_lib::network::connection::{
 
 use IpProtocol::{IPv4, IPv6};
 
-use crate::components::utils::{BASE, DBUS_PATH, WIRELESS};
+use crate::components::daemon::proxy::{network::NetworkProxy, DaemonClient};
 use crate::components::wifi::utils::IpProtocol;
 use crate::components::wifi::wifi_address_entry::WifiAddressEntry;
 use crate::components::wifi::wifi_options_impl;
@@ -389,9 +388,6 @@ fn setup_callbacks(wifi_options: &Arc<WifiOptions>, path: Path<'static>) {
 
 fn set_connection_settings(path: Path<'static>, prop: HashMap<String, PropMap>) {
     gio::spawn_blocking(move || {
-        let conn = dbus::blocking::Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-        let _: Result<(bool,), Error> =
-            proxy.method_call(WIRELESS, "SetConnectionSettings", (path, prop));
+        let _ = DaemonClient::new().set_connection_settings(path, prop);
     });
 }
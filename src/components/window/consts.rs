@@ -1,8 +1,62 @@
+use crate::components::audio::input::source_const;
+use crate::components::audio::output::sink_const;
+use crate::components::daemon::compat::DaemonMethod;
+use crate::components::daemon::proxy::{bluetooth, network};
 use crate::components::plugin::function::ReSetSidebarInfo;
 
 use super::handle_sidebar_click::{
-    HANDLE_AUDIO_CLICK, HANDLE_BLUETOOTH_CLICK, HANDLE_CONNECTIVITY_CLICK, HANDLE_MICROPHONE_CLICK,
-    HANDLE_VOLUME_CLICK, HANDLE_WIFI_CLICK,
+    HANDLE_AUDIO_CLICK, HANDLE_BLUETOOTH_CLICK, HANDLE_CONNECTIVITY_CLICK, HANDLE_HOME,
+    HANDLE_MICROPHONE_CLICK, HANDLE_PLUGINS_CLICK, HANDLE_VOLUME_CLICK, HANDLE_WIFI_CLICK,
+};
+
+pub const WIFI_METHODS: &[DaemonMethod] = &[
+    network::LIST_ACCESS_POINTS.member(),
+    network::LIST_STORED_CONNECTIONS.member(),
+    network::GET_ALL_WIFI_DEVICES.member(),
+    network::GET_WIFI_STATUS.member(),
+    network::START_NETWORK_LISTENER.member(),
+    network::STOP_NETWORK_LISTENER.member(),
+];
+
+pub const BLUETOOTH_METHODS: &[DaemonMethod] = &[
+    bluetooth::GET_BLUETOOTH_ADAPTERS.member(),
+    bluetooth::GET_BLUETOOTH_DEVICES.member(),
+    bluetooth::START_BLUETOOTH_LISTENER.member(),
+    bluetooth::STOP_BLUETOOTH_LISTENER.member(),
+];
+
+pub const SINK_METHODS: &[DaemonMethod] = &[
+    sink_const::GETOBJECTS.function.member(),
+    sink_const::GETDEFAULT.function.member(),
+    sink_const::GETDEFAULTNAME.function.member(),
+    sink_const::GETSTREAMS.function.member(),
+];
+
+pub const SOURCE_METHODS: &[DaemonMethod] = &[
+    source_const::GETOBJECTS.function.member(),
+    source_const::GETDEFAULT.function.member(),
+    source_const::GETDEFAULTNAME.function.member(),
+    source_const::GETSTREAMS.function.member(),
+];
+
+pub const AUDIO_METHODS: &[DaemonMethod] = &[
+    sink_const::GETOBJECTS.function.member(),
+    sink_const::GETDEFAULT.function.member(),
+    sink_const::GETDEFAULTNAME.function.member(),
+    sink_const::GETSTREAMS.function.member(),
+    source_const::GETOBJECTS.function.member(),
+    source_const::GETDEFAULT.function.member(),
+    source_const::GETDEFAULTNAME.function.member(),
+    source_const::GETSTREAMS.function.member(),
+];
+
+pub const HOME_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
+    name: "Home",
+    icon_name: "go-home-symbolic",
+    parent: None,
+    click_event: HANDLE_HOME,
+    required_methods: &[],
+    search_keywords: &[("overview", None), ("dashboard", None)],
 };
 
 pub const CONNECTIVITY_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -10,6 +64,8 @@ pub const CONNECTIVITY_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "network-wired-symbolic",
     parent: None,
     click_event: HANDLE_CONNECTIVITY_CLICK,
+    required_methods: &[],
+    search_keywords: &[("network", None), ("internet", None)],
 };
 
 pub const WIFI_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -17,6 +73,17 @@ pub const WIFI_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "network-wireless-symbolic",
     parent: Some("Connectivity"),
     click_event: HANDLE_WIFI_CLICK,
+    required_methods: WIFI_METHODS,
+    search_keywords: &[
+        ("wireless", None),
+        ("wlan", None),
+        ("WiFi Device", Some("WiFi Device")),
+        ("adapter", Some("WiFi Device")),
+        ("Saved Networks", Some("Saved Networks")),
+        ("password", Some("Saved Networks")),
+        ("Available networks", Some("Available networks")),
+        ("access point", Some("Available networks")),
+    ],
 };
 
 pub const BLUETOOTH_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -24,6 +91,17 @@ pub const BLUETOOTH_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "bluetooth-symbolic",
     parent: Some("Connectivity"),
     click_event: HANDLE_BLUETOOTH_CLICK,
+    required_methods: BLUETOOTH_METHODS,
+    search_keywords: &[
+        ("Bluetooth Adapter", Some("Bluetooth Adapter")),
+        ("Visibility Settings", Some("Visibility Settings")),
+        ("Pairable", Some("Pairable")),
+        ("Discoverable", Some("Discoverable")),
+        ("pair", Some("Available Devices")),
+        ("Available Devices", Some("Available Devices")),
+        ("Connected Devices", Some("Connected Devices")),
+        ("Saved Devices", Some("Saved Devices")),
+    ],
 };
 
 pub const AUDIO_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -31,6 +109,8 @@ pub const AUDIO_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "audio-headset-symbolic",
     parent: None,
     click_event: HANDLE_AUDIO_CLICK,
+    required_methods: AUDIO_METHODS,
+    search_keywords: &[("sound", None), ("volume", None)],
 };
 
 pub const SINK_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -38,6 +118,16 @@ pub const SINK_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "audio-volume-high-symbolic",
     parent: Some("Audio"),
     click_event: HANDLE_VOLUME_CLICK,
+    required_methods: SINK_METHODS,
+    search_keywords: &[
+        ("speaker", None),
+        ("headphones", None),
+        ("Default Output Device", Some("Default Output Device")),
+        ("mute", Some("Default Output Device")),
+        ("Output Devices", Some("Output Devices")),
+        ("Output Streams", Some("Output Streams")),
+        ("Profile Settings", Some("Profile Settings")),
+    ],
 };
 
 pub const SOURCE_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
@@ -45,4 +135,35 @@ pub const SOURCE_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
     icon_name: "audio-input-microphone-symbolic",
     parent: Some("Audio"),
     click_event: HANDLE_MICROPHONE_CLICK,
+    required_methods: SOURCE_METHODS,
+    search_keywords: &[
+        ("microphone", None),
+        ("recording", None),
+        ("Default Input Device", Some("Default Input Device")),
+        ("mute", Some("Default Input Device")),
+        ("Input Devices", Some("Input Devices")),
+        ("Input Streams", Some("Input Streams")),
+        ("Profile Settings", Some("Profile Settings")),
+    ],
+};
+
+pub const PLUGINS_SIDEBAR: ReSetSidebarInfo = ReSetSidebarInfo {
+    name: "Plugins",
+    icon_name: "application-x-addon-symbolic",
+    parent: None,
+    click_event: HANDLE_PLUGINS_CLICK,
+    required_methods: &[],
+    search_keywords: &[("extensions", None), ("add-ons", None)],
 };
+
+/// Plugins may not reuse these names, as pages are looked up by the name of their entry.
+pub const BUILTIN_SIDEBAR_NAMES: &[&str] = &[
+    HOME_SIDEBAR.name,
+    CONNECTIVITY_SIDEBAR.name,
+    WIFI_SIDEBAR.name,
+    BLUETOOTH_SIDEBAR.name,
+    AUDIO_SIDEBAR.name,
+    SINK_SIDEBAR.name,
+    SOURCE_SIDEBAR.name,
+    PLUGINS_SIDEBAR.name,
+];
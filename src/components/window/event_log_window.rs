@@ -0,0 +1,121 @@
+use adw::prelude::ActionRowExt;
+use adw::{ActionRow, HeaderBar, StatusPage, ToolbarView};
+use glib::clone;
+use gtk::prelude::*;
+use gtk::{Align, Button, ListBox, ScrolledWindow, SelectionMode, Stack};
+
+use crate::components::base::event_log::{
+    clear_event_log, event_log_text, logged_events, LoggedEvent,
+};
+
+/// Lists the failed daemon calls, the newest first.
+/// The window is only hidden when closed, so that it can be shown again.
+#[derive(Clone)]
+pub struct EventLogWindow {
+    pub window: adw::Window,
+    list: ListBox,
+    stack: Stack,
+}
+
+impl EventLogWindow {
+    pub fn new(parent: &impl IsA<gtk::Window>) -> Self {
+        let list = ListBox::builder()
+            .selection_mode(SelectionMode::None)
+            .valign(Align::Start)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .css_classes(["boxed-list"])
+            .build();
+        let empty = StatusPage::builder()
+            .icon_name("emblem-ok-symbolic")
+            .title("No errors")
+            .description("Failed daemon calls are listed here.")
+            .build();
+        let stack = Stack::new();
+        stack.add_named(&empty, Some("empty"));
+        stack.add_named(
+            &ScrolledWindow::builder().child(&list).vexpand(true).build(),
+            Some("events"),
+        );
+
+        let clear_button = Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Clear")
+            .build();
+        let copy_button = Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .tooltip_text("Copy all")
+            .build();
+        let header = HeaderBar::new();
+        header.pack_start(&clear_button);
+        header.pack_end(&copy_button);
+        let toolbar = ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&stack));
+
+        let window = adw::Window::builder()
+            .title("Event Log")
+            .default_width(600)
+            .default_height(450)
+            .transient_for(parent)
+            .hide_on_close(true)
+            .content(&toolbar)
+            .build();
+
+        copy_button.connect_clicked(|button| {
+            button
+                .clipboard()
+                .set_text(&event_log_text(&logged_events()));
+        });
+        clear_button.connect_clicked(clone!(@weak list, @weak stack => move |_| {
+            clear_event_log();
+            show_events(&list, &stack);
+        }));
+        Self {
+            window,
+            list,
+            stack,
+        }
+    }
+
+    pub fn present(&self) {
+        self.refresh();
+        self.window.present();
+    }
+
+    /// Shows the events logged so far.
+    pub fn refresh(&self) {
+        show_events(&self.list, &self.stack);
+    }
+}
+
+fn show_events(list: &ListBox, stack: &Stack) {
+    list.remove_all();
+    let events = logged_events();
+    for event in &events {
+        list.append(&create_event_row(event));
+    }
+    stack.set_visible_child_name(if events.is_empty() { "empty" } else { "events" });
+}
+
+fn create_event_row(event: &LoggedEvent) -> ActionRow {
+    let row = ActionRow::builder()
+        .title(&event.method)
+        .subtitle(format!("{}\n{}", event.time(), event.message))
+        .use_markup(false)
+        .build();
+    let copy_button = Button::builder()
+        .icon_name("edit-copy-symbolic")
+        .tooltip_text("Copy")
+        .valign(Align::Center)
+        .has_frame(false)
+        .build();
+    let line = event.to_line();
+    copy_button.connect_clicked(move |button| {
+        button.clipboard().set_text(&line);
+    });
+    row.add_suffix(&copy_button);
+    row
+}
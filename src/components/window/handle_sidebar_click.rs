@@ -1,17 +1,20 @@
 use gtk::prelude::FrameExt;
 use std::cell::RefCell;
-use std::hint::spin_loop;
 use std::rc::Rc;
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
-use crate::components::audio::input::source_box::{populate_sources, SourceBox};
-use crate::components::audio::output::sink_box::{populate_sinks, SinkBox};
+use crate::components::audio::input::source_box::SourceBox;
+use crate::components::audio::output::sink_box::SinkBox;
 use crate::components::base::setting_box::SettingBox;
 use crate::components::base::utils::{start_audio_listener, Listeners, Position};
 use crate::components::bluetooth::bluetooth_box::{
     populate_connected_bluetooth_devices, BluetoothBox,
 };
+use crate::components::home::home_page::{create_home_cards, open_plugin_on_click};
+use crate::components::plugin::extension::{
+    extension_widgets, plugin_extension_widgets, ExtensionPoint,
+};
+use crate::components::plugin::plugin_page::create_plugin_page;
 use crate::components::utils::Capabilities;
 use crate::components::wifi::wifi_box::{
     scan_for_wifi, show_stored_connections, start_event_listener, WifiBox,
@@ -53,6 +56,7 @@ pub const HANDLE_CONNECTIVITY_CLICK: fn(
         count += 1;
     }
 
+    count += insert_page_extensions(ExtensionPoint::ConnectivityPage, &reset_main);
     reset_main.set_max_children_per_line(count);
 };
 
@@ -112,17 +116,13 @@ pub const HANDLE_AUDIO_CLICK: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<RefC
             Some(audio_output.clone()),
             Some(audio_input.clone()),
         );
-        if !listeners.pulse_listener.load(Ordering::SeqCst) {
-            spin_loop();
-        }
-        populate_sinks(audio_output.clone());
-        populate_sources(audio_input.clone());
         let sink_frame = wrap_in_flow_box_child(SettingBox::new(&*audio_output));
         let source_frame = wrap_in_flow_box_child(SettingBox::new(&*audio_input));
         reset_main.remove_all();
         reset_main.insert(&sink_frame, -1);
         reset_main.insert(&source_frame, -1);
-        reset_main.set_max_children_per_line(2);
+        let extensions = insert_page_extensions(ExtensionPoint::AudioPage, &reset_main);
+        reset_main.set_max_children_per_line(2 + extensions);
     };
 
 pub const HANDLE_VOLUME_CLICK: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<RefCell<Position>>) =
@@ -134,11 +134,7 @@ pub const HANDLE_VOLUME_CLICK: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<Ref
             return;
         }
         let audio_output = Arc::new(SinkBox::new());
-        start_audio_listener(listeners.clone(), Some(audio_output.clone()), None);
-        if !listeners.pulse_listener.load(Ordering::SeqCst) {
-            spin_loop();
-        }
-        populate_sinks(audio_output.clone());
+        start_audio_listener(listeners, Some(audio_output.clone()), None);
         let audio_frame = wrap_in_flow_box_child(SettingBox::new(&*audio_output));
         reset_main.remove_all();
         reset_main.insert(&audio_frame, -1);
@@ -158,26 +154,52 @@ pub const HANDLE_MICROPHONE_CLICK: fn(
         return;
     }
     let audio_input = Arc::new(SourceBox::new());
-    start_audio_listener(listeners.clone(), None, Some(audio_input.clone()));
-    if !listeners.pulse_listener.load(Ordering::SeqCst) {
-        spin_loop();
-    }
-    populate_sources(audio_input.clone());
+    start_audio_listener(listeners, None, Some(audio_input.clone()));
     let source_frame = wrap_in_flow_box_child(SettingBox::new(&*audio_input));
     reset_main.remove_all();
     reset_main.insert(&source_frame, -1);
     reset_main.set_max_children_per_line(1);
 };
 
-pub const HANDLE_HOME: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<RefCell<Position>>) =
+pub const HANDLE_PLUGINS_CLICK: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<RefCell<Position>>) =
     |_: &Capabilities,
      listeners: Arc<Listeners>,
      reset_main: FlowBox,
      position: Rc<RefCell<Position>>| {
-        if handle_init(listeners, position, Position::Home) {
+        if handle_init(listeners, position, Position::Plugins) {
+            return;
+        }
+        let plugin_frame = wrap_in_flow_box_child(SettingBox::new(&create_plugin_page()));
+        reset_main.remove_all();
+        reset_main.insert(&plugin_frame, -1);
+        reset_main.set_max_children_per_line(1);
+    };
+
+pub const HANDLE_HOME: fn(&Capabilities, Arc<Listeners>, FlowBox, Rc<RefCell<Position>>) =
+    |capabilities: &Capabilities,
+     listeners: Arc<Listeners>,
+     reset_main: FlowBox,
+     position: Rc<RefCell<Position>>| {
+        // home is also set while no page is shown, e.g. when the current page is reloaded
+        if handle_init(listeners, position, Position::Home) && reset_main.first_child().is_some() {
             return;
         }
         reset_main.remove_all();
+
+        let cards = create_home_cards(capabilities);
+        let mut count = cards.len() as u32;
+        for card in &cards {
+            reset_main.insert(&wrap_in_flow_box_child(SettingBox::new(card)), -1);
+        }
+
+        let tiles = plugin_extension_widgets(ExtensionPoint::HomePage);
+        for (index, plugin_file, widget) in &tiles {
+            let tile = wrap_in_flow_box_child(SettingBox::new(widget));
+            open_plugin_on_click(&tile, plugin_file);
+            reset_main.insert(&tile, *index);
+        }
+        count += tiles.len() as u32;
+        reset_main.set_max_children_per_line(count.min(3));
     };
 
 fn wrap_in_flow_box_child(widget: SettingBox) -> FlowBoxChild {
@@ -191,6 +213,15 @@ fn wrap_in_flow_box_child(widget: SettingBox) -> FlowBoxChild {
         .build()
 }
 
+/// Adds the cards plugins registered for a page, returns how many were added.
+fn insert_page_extensions(point: ExtensionPoint, reset_main: &FlowBox) -> u32 {
+    let extensions = extension_widgets(point);
+    for (index, widget) in &extensions {
+        reset_main.insert(&wrap_in_flow_box_child(SettingBox::new(widget)), *index);
+    }
+    extensions.len() as u32
+}
+
 fn handle_init(
     listeners: Arc<Listeners>,
     position: Rc<RefCell<Position>>,
@@ -206,5 +237,6 @@ fn handle_init(
     listeners.stop_network_listener();
     listeners.stop_audio_listener();
     listeners.stop_bluetooth_listener();
+    listeners.leave_plugin_page();
     false
 }
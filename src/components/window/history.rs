@@ -0,0 +1,128 @@
+use adw::prelude::NavigationPageExt;
+use adw::{NavigationPage, NavigationView};
+use glib::prelude::Cast;
+use gtk::prelude::{ListModelExt, WidgetExt};
+
+use crate::components::base::utils::Position;
+
+/// Older entries are dropped once the history is longer than this.
+const MAX_HISTORY: usize = 50;
+
+/// A visited page together with the sub pages that were open on it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub position: Position,
+    /// Tags of the pages of each navigation view on the page, in widget order.
+    /// Pages without a tag are recorded as an empty string.
+    pub pages: Vec<Vec<String>>,
+}
+
+#[derive(Default)]
+pub struct NavigationHistory {
+    back: Vec<HistoryEntry>,
+    current: Option<HistoryEntry>,
+    forward: Vec<HistoryEntry>,
+}
+
+impl NavigationHistory {
+    /// Records that an entry is shown now.
+    /// Leaving a sub page by other means than the history, e.g. its back button,
+    /// moves through the history instead of adding to it.
+    pub fn record(&mut self, entry: HistoryEntry) {
+        let current = match &self.current {
+            Some(current) if *current == entry => return,
+            Some(current) => current,
+            None => {
+                self.current = Some(entry);
+                return;
+            }
+        };
+        if current.position == entry.position {
+            if self.back.last() == Some(&entry) {
+                self.go_back();
+                return;
+            }
+            if self.forward.last() == Some(&entry) {
+                self.go_forward();
+                return;
+            }
+        }
+        if let Some(current) = self.current.replace(entry) {
+            self.back.push(current);
+        }
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Replaces the current entry without adding to the history,
+    /// e.g. when an entry could only be restored partially.
+    pub fn replace_current(&mut self, entry: HistoryEntry) {
+        self.current = Some(entry);
+    }
+
+    /// Moves back by one entry and returns the entry to show.
+    pub fn go_back(&mut self) -> Option<HistoryEntry> {
+        let previous = self.back.pop()?;
+        if let Some(current) = self.current.replace(previous.clone()) {
+            self.forward.push(current);
+        }
+        Some(previous)
+    }
+
+    /// Moves forward by one entry and returns the entry to show.
+    pub fn go_forward(&mut self) -> Option<HistoryEntry> {
+        let next = self.forward.pop()?;
+        if let Some(current) = self.current.replace(next.clone()) {
+            self.back.push(current);
+        }
+        Some(next)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+}
+
+/// The navigation views of a page, in widget order.
+pub fn navigation_views(page: &gtk::Widget) -> Vec<NavigationView> {
+    let mut views = Vec::new();
+    let mut child = page.first_child();
+    while let Some(widget) = child {
+        match widget.clone().downcast::<NavigationView>() {
+            Ok(view) => views.push(view),
+            Err(widget) => views.extend(navigation_views(&widget)),
+        }
+        child = widget.next_sibling();
+    }
+    views
+}
+
+/// Tags of the pages of a navigation view, from the root page to the visible one.
+pub fn navigation_tags(view: &NavigationView) -> Vec<String> {
+    let stack = view.navigation_stack();
+    (0..stack.n_items())
+        .filter_map(|index| stack.item(index))
+        .filter_map(|page| page.downcast::<NavigationPage>().ok())
+        .map(|page| page.tag().map(String::from).unwrap_or_default())
+        .collect()
+}
+
+/// Shows the pages of a recorded stack again, as far as they can be found by their tag.
+/// Pages created on demand, like the WiFi options, have no tag and are left out.
+pub fn restore_navigation_tags(view: &NavigationView, tags: &[String]) {
+    let available: Vec<&str> = tags
+        .iter()
+        .take_while(|tag| !tag.is_empty() && view.find_page(tag).is_some())
+        .map(String::as_str)
+        .collect();
+    if available.is_empty() || navigation_tags(view) == available {
+        return;
+    }
+    view.replace_with_tags(&available);
+}
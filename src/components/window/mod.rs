@@ -1,6 +1,10 @@
 pub mod handle_sidebar_click;
+pub mod history;
 pub mod reset_window;
 pub mod reset_window_impl;
+pub mod search;
 pub mod sidebar_entry;
 pub mod sidebar_entry_impl;
 pub mod consts;
+pub mod event_log_window;
+pub mod quick_settings;
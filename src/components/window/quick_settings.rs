@@ -0,0 +1,174 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use adw::prelude::AdwWindowExt;
+use adw::{PreferencesGroup, ToastOverlay};
+use glib::subclass::types::ObjectSubclassIsExt;
+use gtk::prelude::*;
+use gtk::{
+    Application, NamedAction, Orientation, PolicyType, ScrolledWindow, Shortcut,
+    ShortcutController, ShortcutTrigger,
+};
+
+use crate::components::audio::input::source_box::SourceBox;
+use crate::components::audio::output::sink_box::SinkBox;
+use crate::components::base::setting_box::SettingBox;
+use crate::components::base::utils::{start_audio_listener, Listeners};
+use crate::components::bluetooth::bluetooth_box::{
+    populate_connected_bluetooth_devices, BluetoothBox,
+};
+use crate::components::daemon::compat::DaemonCompatibility;
+use crate::components::utils::get_capabilities;
+use crate::components::wifi::wifi_box::{scan_for_wifi, start_event_listener, WifiBox};
+use crate::components::window::consts::{BLUETOOTH_METHODS, WIFI_METHODS};
+use crate::components::window::reset_window::run_pending_actions;
+use crate::components::window::reset_window_impl::PendingActions;
+
+/// Widget name of the quick settings window, used to find an already open one.
+const QUICK_SETTINGS_NAME: &str = "resetQuickSettings";
+
+thread_local! {
+    // only a single quick settings window is open at a time
+    static PENDING_ACTIONS: PendingActions = PendingActions::default();
+}
+
+/// Shows the compact quick settings window, or presents the one that is already open.
+pub fn show_quick_settings(app: &Application, compatibility: &DaemonCompatibility) {
+    let open = app
+        .windows()
+        .into_iter()
+        .find(|window| window.widget_name() == QUICK_SETTINGS_NAME);
+    match open {
+        Some(window) => window.present(),
+        None => create_quick_settings(app, compatibility).present(),
+    }
+}
+
+/// Builds an undecorated window with the boxes of the main window,
+/// reduced to the controls needed in passing. It closes once it loses focus.
+fn create_quick_settings(app: &Application, compatibility: &DaemonCompatibility) -> adw::Window {
+    // the window has its own listeners, those of the daemon are shared with the main window
+    let listeners = Arc::new(Listeners::default());
+    let capabilities = get_capabilities();
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    if capabilities.contains(&"Audio".to_string()) {
+        let sink_box = Arc::new(SinkBox::without_extensions());
+        let source_box = Arc::new(SourceBox::new());
+        compact_sink_box(&sink_box);
+        compact_source_box(&source_box);
+        start_audio_listener(
+            listeners.clone(),
+            Some(sink_box.clone()),
+            Some(source_box.clone()),
+        );
+        content.append(&SettingBox::new(&*sink_box));
+        content.append(&SettingBox::new(&*source_box));
+    }
+
+    if capabilities.contains(&"WiFi".to_string()) && compatibility.supports(WIFI_METHODS) {
+        let wifi_box = WifiBox::without_extensions(listeners.clone());
+        // only the switch and the available networks are shown
+        wifi_box.imp().reset_wifi_details.set_visible(false);
+        start_event_listener(listeners.clone(), wifi_box.clone());
+        scan_for_wifi(wifi_box.clone());
+        content.append(&SettingBox::new(&*wifi_box));
+    }
+
+    if capabilities.contains(&"Bluetooth".to_string()) && compatibility.supports(BLUETOOTH_METHODS)
+    {
+        let bluetooth_box = BluetoothBox::without_extensions(listeners.clone());
+        compact_bluetooth_box(&bluetooth_box);
+        populate_connected_bluetooth_devices(listeners.clone(), bluetooth_box.clone());
+        content.append(&SettingBox::new(&*bluetooth_box));
+    }
+
+    let scrolled_window = ScrolledWindow::builder()
+        .hscrollbar_policy(PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(700)
+        .child(&content)
+        .build();
+    // shows the undo toasts of removed devices
+    let toast_overlay = ToastOverlay::builder().child(&scrolled_window).build();
+    let window = adw::Window::builder()
+        .application(app)
+        .title("Quick Settings")
+        .decorated(false)
+        .default_width(400)
+        .content(&toast_overlay)
+        .build();
+    window.set_widget_name(QUICK_SETTINGS_NAME);
+
+    let shortcuts = ShortcutController::new();
+    shortcuts.add_shortcut(Shortcut::new(
+        ShortcutTrigger::parse_string("Escape"),
+        Some(NamedAction::new("window.close")),
+    ));
+    window.add_controller(shortcuts);
+
+    window.connect_is_active_notify(|window| {
+        if !window.is_active() {
+            window.close();
+        }
+    });
+    window.connect_close_request(move |_| {
+        PENDING_ACTIONS.with(run_pending_actions);
+        listeners.stop_network_listener();
+        listeners.stop_audio_listener();
+        listeners.stop_bluetooth_listener();
+        glib::Propagation::Proceed
+    });
+    window
+}
+
+/// The toast overlay and pending undo actions of the quick settings window, if it is the root.
+pub fn quick_settings_toasts(root: &gtk::Root) -> Option<(ToastOverlay, PendingActions)> {
+    if root.widget_name() != QUICK_SETTINGS_NAME {
+        return None;
+    }
+    let overlay = root
+        .downcast_ref::<adw::Window>()?
+        .content()?
+        .downcast::<ToastOverlay>()
+        .ok()?;
+    Some((overlay, PENDING_ACTIONS.with(Rc::clone)))
+}
+
+/// Only keeps the default output device and its volume.
+fn compact_sink_box(sink_box: &SinkBox) {
+    let imp = sink_box.imp();
+    imp.reset_sinks_row.set_visible(false);
+    imp.reset_cards_row.set_visible(false);
+    hide_streams(&imp.reset_input_streams);
+}
+
+/// Only keeps the volume of the default input device.
+fn compact_source_box(source_box: &SourceBox) {
+    let imp = source_box.imp();
+    imp.reset_source_row.set_visible(false);
+    imp.reset_cards_row.set_visible(false);
+    imp.reset_source_dropdown.set_visible(false);
+    hide_streams(&imp.reset_output_streams);
+}
+
+/// Only keeps the switch and the connected and saved devices.
+fn compact_bluetooth_box(bluetooth_box: &BluetoothBox) {
+    let imp = bluetooth_box.imp();
+    // the adapter and visibility rows share a group without an id
+    if let Some(details) = imp
+        .reset_bluetooth_adapter
+        .ancestor(PreferencesGroup::static_type())
+    {
+        details.set_visible(false);
+    }
+    imp.reset_bluetooth_available_devices.set_visible(false);
+}
+
+/// Hides the streams of an audio box together with the separator above them.
+fn hide_streams(streams: &gtk::Box) {
+    streams.set_visible(false);
+    if let Some(separator) = streams.prev_sibling() {
+        separator.set_visible(false);
+    }
+}
@@ -1,31 +1,69 @@
 use std::cell::RefCell;
+use std::path::Path;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use adw::glib::clone;
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use adw::BreakpointCondition;
-use glib::Object;
+use adw::{BreakpointCondition, ToastOverlay};
+use glib::{Object, SendWeakRef};
 use gtk::gio::ActionEntry;
 use gtk::{
-    gio, AccessibleRole, Align, Application, FlowBox, FlowBoxChild, Frame, ListBoxRow, Orientation,
-    StateFlags,
+    gio, AccessibleRole, Align, Application, FlowBox, FlowBoxChild, Frame, GestureClick,
+    ListBoxRow, Orientation, PropagationPhase, StateFlags,
 };
 use gtk::{prelude::*, DirectionType};
 use re_set_lib::utils::plugin_setup::FRONTEND_PLUGINS;
+use re_set_lib::ERROR;
+#[cfg(debug_assertions)]
+use re_set_lib::{utils::macros::ErrorLevel, write_log_to_file};
 
+use crate::components::base::event_log::{
+    connect_event_logged, disconnect_event_logged, LoggedEvent,
+};
 use crate::components::base::setting_box::SettingBox;
 use crate::components::base::utils::{Listeners, Position};
+use crate::components::config::frontend::{frontend_config, watch_frontend_config, FrontendConfig};
+use crate::components::config::state::{record_window_state, WindowState};
+use crate::components::daemon::compat::DaemonCompatibility;
+use crate::components::daemon::readiness::{daemon_timeout, register_client, retry_daemon};
+use crate::components::daemon::reconnect::{watch_daemon_name, DaemonEvent};
+use crate::components::plugin::abi::check_plugin_abi;
+use crate::components::plugin::extension::{
+    plugin_extensions, register_extensions, set_enabled_extension_plugins,
+    set_extension_capabilities, unregister_extensions,
+};
 use crate::components::plugin::function::PluginSidebarInfo;
+use crate::components::plugin::host::{provide_host, set_host_window};
+use crate::components::plugin::manager::{
+    call_plugin, caught_panic, disable_for_session, enable_for_session, library_file_name,
+    mark_started, panic_taker, plugin_hooks, plugin_infos, plugin_kind, plugin_library,
+    session_status, set_plugin_infos, shutdown_plugin, validate_plugin_data, PluginError,
+    PluginInfo, PluginKind, PluginStatus,
+};
+use crate::components::plugin::manifest::read_manifests;
+use crate::components::plugin::manifest_page::{
+    create_manifest_page, listen_for_signals, refresh_rows,
+};
+use crate::components::plugin::reload::{replace_plugin, watch_plugin_libraries};
+use crate::components::plugin::search::{plugin_search_keywords, SearchKeyword};
 use crate::components::utils::get_capabilities;
+use crate::components::window::event_log_window::EventLogWindow;
 use crate::components::window::handle_sidebar_click::*;
-use crate::components::window::reset_window_impl;
+use crate::components::window::history::{
+    navigation_tags, navigation_views, restore_navigation_tags, HistoryEntry, NavigationHistory,
+};
+use crate::components::window::quick_settings::quick_settings_toasts;
+use crate::components::window::reset_window_impl::{self, PendingAction, PendingActions};
+use crate::components::window::search::{entry_matches, highlight_row, matching_row};
 use crate::components::window::sidebar_entry::SidebarEntry;
 use crate::VERSION;
 
 use super::consts::{
-    AUDIO_SIDEBAR, BLUETOOTH_SIDEBAR, CONNECTIVITY_SIDEBAR, SINK_SIDEBAR, SOURCE_SIDEBAR,
-    WIFI_SIDEBAR,
+    AUDIO_SIDEBAR, BLUETOOTH_METHODS, BLUETOOTH_SIDEBAR, BUILTIN_SIDEBAR_NAMES,
+    CONNECTIVITY_SIDEBAR, HOME_SIDEBAR, PLUGINS_SIDEBAR, SINK_METHODS, SINK_SIDEBAR,
+    SOURCE_SIDEBAR, WIFI_METHODS, WIFI_SIDEBAR,
 };
 
 glib::wrapper! {
@@ -40,10 +78,17 @@ unsafe impl Send for ReSetWindow {}
 unsafe impl Sync for ReSetWindow {}
 
 impl ReSetWindow {
-    pub fn new(app: &Application) -> Rc<Self> {
+    pub fn new(
+        app: &Application,
+        daemon_ready: Arc<AtomicBool>,
+        compatibility: DaemonCompatibility,
+        state: WindowState,
+    ) -> Rc<Self> {
         app.set_accels_for_action("win.search", &["<Ctrl>F"]);
         app.set_accels_for_action("win.close", &["<Ctrl>Q"]);
         app.set_accels_for_action("win.about", &["<Ctrl>H"]);
+        app.set_accels_for_action("win.back", &["<Alt>Left"]);
+        app.set_accels_for_action("win.forward", &["<Alt>Right"]);
         // implemented when a proper movement method is found
         // app.set_accels_for_action("win.up", &["<Ctrl>K"]);
         // app.set_accels_for_action("win.right", &["<Ctrl>L"]);
@@ -51,9 +96,75 @@ impl ReSetWindow {
         // app.set_accels_for_action("win.left", &["<Ctrl>H"]);
         let mut window: Rc<Self> = Rc::new(Object::builder().property("application", app).build());
         window = setup_callback(window);
+        window.imp().compatibility.replace(compatibility);
+        // a size set in the config takes precedence over the one of the last session
+        if let Some((width, height)) = frontend_config().window_size.or(state.size) {
+            window.set_default_size(width, height);
+        }
+        window
+            .imp()
+            .reset_overlay_split_view
+            .set_show_sidebar(!state.sidebar_collapsed);
+        window.imp().restored_position.replace(state.position);
+        window.watch_config();
+        window.watch_event_log();
+        if daemon_ready.load(Ordering::SeqCst) {
+            window.setup_sidebar_entries();
+        } else {
+            window.show_daemon_unavailable(daemon_ready);
+        }
         window
     }
 
+    /// Replaces the main content with a page that allows retrying the daemon connection.
+    pub fn show_daemon_unavailable(&self, daemon_ready: Arc<AtomicBool>) {
+        let self_imp = self.imp();
+        let retry_button = gtk::Button::builder()
+            .label("Retry")
+            .halign(Align::Center)
+            .css_classes(["pill", "suggested-action"])
+            .build();
+        let status_page = adw::StatusPage::builder()
+            .icon_name("dialog-warning-symbolic")
+            .title("Daemon unavailable")
+            .description("ReSet could not reach the daemon, make sure it is installed and running.")
+            .child(&retry_button)
+            .build();
+        // restored once the daemon is reachable, not every page sets its own
+        let max_children_per_line = self_imp.reset_main.max_children_per_line();
+        self_imp.reset_main.remove_all();
+        self_imp.reset_main.insert(&status_page, -1);
+        self_imp.reset_main.set_max_children_per_line(1);
+
+        retry_button.connect_clicked(clone!(@weak self as window => move |button| {
+            button.set_sensitive(false);
+            button.set_label("Connecting...");
+            let daemon_ready = daemon_ready.clone();
+            let button = button.clone();
+            glib::spawn_future_local(async move {
+                let ready = daemon_ready.clone();
+                let compatibility = gio::spawn_blocking(move || {
+                    retry_daemon(&ready, daemon_timeout()).then(DaemonCompatibility::query)
+                })
+                .await
+                .unwrap_or(None);
+                if let Some(compatibility) = compatibility {
+                    window.imp().compatibility.replace(compatibility);
+                    window.imp().reset_main.remove_all();
+                    window
+                        .imp()
+                        .reset_main
+                        .set_max_children_per_line(max_children_per_line);
+                    window.setup_sidebar_entries();
+                } else {
+                    ERROR!("ReSet daemon still unavailable", ErrorLevel::Recoverable);
+                    button.set_label("Retry");
+                    button.set_sensitive(true);
+                }
+            });
+        }));
+    }
+
     pub fn handle_dynamic_sidebar(&self) {
         let self_imp = self.imp();
         self_imp
@@ -81,25 +192,23 @@ impl ReSetWindow {
                 }
                 continue;
             }
-            if main_entry
-                .imp()
-                .name
-                .borrow()
-                .to_lowercase()
-                .contains(&text.to_lowercase())
-            {
+            let entry_imp = main_entry.imp();
+            if entry_matches(
+                &entry_imp.name.borrow(),
+                &entry_imp.search_keywords.borrow(),
+                &text,
+            ) {
                 main_entry.set_visible(true);
             } else {
                 main_entry.set_visible(false);
             }
             for sub_entry in sub_entriess {
-                if sub_entry
-                    .imp()
-                    .name
-                    .borrow()
-                    .to_lowercase()
-                    .contains(&text.to_lowercase())
-                {
+                let entry_imp = sub_entry.imp();
+                if entry_matches(
+                    &entry_imp.name.borrow(),
+                    &entry_imp.search_keywords.borrow(),
+                    &text,
+                ) {
                     sub_entry.set_visible(true);
                     main_entry.set_visible(true);
                 } else {
@@ -109,6 +218,242 @@ impl ReSetWindow {
         }
     }
 
+    /// Opens the page of the given position as if its sidebar entry had been clicked.
+    pub fn navigate_to(&self, position: Position) {
+        if let Some(entry) = self.find_sidebar_entry(&position) {
+            self.imp().reset_sidebar_list.select_row(Some(&entry));
+            entry.emit_activate();
+            return;
+        }
+        ERROR!(
+            format!("No sidebar entry found for page {:?}", position),
+            ErrorLevel::Recoverable
+        );
+    }
+
+    fn find_sidebar_entry(&self, position: &Position) -> Option<SidebarEntry> {
+        let name = position.sidebar_name()?;
+        let mut child = self.imp().reset_sidebar_list.first_child();
+        while let Some(widget) = child {
+            if let Ok(entry) = widget.clone().downcast::<SidebarEntry>() {
+                if *entry.imp().name.borrow() == name {
+                    return Some(entry);
+                }
+            }
+            child = widget.next_sibling();
+        }
+        None
+    }
+
+    /// The page can only be opened if its entry is shown and supported by the daemon.
+    fn can_open(&self, position: &Position) -> bool {
+        self.find_sidebar_entry(position)
+            .is_some_and(|entry| entry.is_sensitive())
+    }
+
+    /// Current state of the window, to be restored on the next launch.
+    pub fn window_state(&self) -> WindowState {
+        let self_imp = self.imp();
+        let (width, height) = self.default_size();
+        let position = self_imp.position.borrow().clone();
+        let position = if position == Position::Home {
+            // keep the page of the last session if the sidebar was never set up
+            self_imp.restored_position.borrow().clone()
+        } else {
+            Some(position)
+        };
+        WindowState {
+            size: (width > 0 && height > 0).then_some((width, height)),
+            sidebar_collapsed: !self_imp.reset_overlay_split_view.shows_sidebar(),
+            position,
+        }
+    }
+
+    /// Shows the current page again, e.g. to rebuild it after the daemon or the config changed.
+    /// Falls back to an empty page if the entry of the current page is no longer shown.
+    fn reload_page(&self) {
+        let self_imp = self.imp();
+        // sub pages that were open are shown again as well
+        let mut entry = self.current_history_entry();
+        entry.position = self_imp.position.replace(Position::Home);
+        self_imp.reset_main.remove_all();
+        if self.find_sidebar_entry(&entry.position).is_some() {
+            self.restore_history_entry(entry);
+        } else {
+            self_imp.listeners.stop_network_listener();
+            self_imp.listeners.stop_audio_listener();
+            self_imp.listeners.stop_bluetooth_listener();
+            self_imp.listeners.leave_plugin_page();
+        }
+    }
+
+    /// Records the current page in the history and follows the sub pages opened on it.
+    fn record_navigation(&self) {
+        let self_imp = self.imp();
+        let views = navigation_views(self_imp.reset_main.upcast_ref());
+        if *self_imp.navigation_views.borrow() != views {
+            for view in &views {
+                view.connect_visible_page_notify(clone!(@weak self as window => move |_| {
+                    window.record_history_entry();
+                }));
+            }
+            self_imp.navigation_views.replace(views);
+        }
+        self.record_history_entry();
+    }
+
+    fn current_history_entry(&self) -> HistoryEntry {
+        let self_imp = self.imp();
+        HistoryEntry {
+            position: self_imp.position.borrow().clone(),
+            pages: self_imp
+                .navigation_views
+                .borrow()
+                .iter()
+                .map(navigation_tags)
+                .collect(),
+        }
+    }
+
+    fn record_history_entry(&self) {
+        let self_imp = self.imp();
+        if self_imp.restoring_history.get() {
+            return;
+        }
+        let entry = self.current_history_entry();
+        self_imp.history.borrow_mut().record(entry);
+        self.update_history_actions();
+    }
+
+    /// Shows a page of the history again, including its sub pages as far as possible.
+    fn restore_history_entry(&self, entry: HistoryEntry) {
+        let self_imp = self.imp();
+        self_imp.restoring_history.set(true);
+        self.navigate_to(entry.position);
+        for (view, tags) in self_imp.navigation_views.borrow().iter().zip(&entry.pages) {
+            restore_navigation_tags(view, tags);
+        }
+        self_imp.restoring_history.set(false);
+        let restored = self.current_history_entry();
+        self_imp.history.borrow_mut().replace_current(restored);
+        self.update_history_actions();
+    }
+
+    pub fn go_back(&self) {
+        self.move_in_history(NavigationHistory::go_back);
+    }
+
+    pub fn go_forward(&self) {
+        self.move_in_history(NavigationHistory::go_forward);
+    }
+
+    fn move_in_history(&self, step: fn(&mut NavigationHistory) -> Option<HistoryEntry>) {
+        let self_imp = self.imp();
+        loop {
+            let entry = step(&mut self_imp.history.borrow_mut());
+            match entry {
+                // pages that are no longer shown, e.g. of a removed plugin, are skipped
+                Some(entry) if !self.can_open(&entry.position) => continue,
+                Some(entry) => return self.restore_history_entry(entry),
+                None => break,
+            }
+        }
+        let current = self.current_history_entry();
+        self_imp.history.borrow_mut().replace_current(current);
+        self.update_history_actions();
+    }
+
+    fn update_history_actions(&self) {
+        let (back, forward) = {
+            let history = self.imp().history.borrow();
+            (history.can_go_back(), history.can_go_forward())
+        };
+        for (name, enabled) in [("back", back), ("forward", forward)] {
+            if let Some(action) = self
+                .lookup_action(name)
+                .and_then(|action| action.downcast::<gio::SimpleAction>().ok())
+            {
+                action.set_enabled(enabled);
+            }
+        }
+    }
+
+    /// Raises a toast for every failed daemon call and keeps an open event log up to date.
+    fn watch_event_log(&self) {
+        let listener = connect_event_logged(clone!(@weak self as window => move |event| {
+            window.show_error_toast(event);
+            if let Some(event_log) = window.imp().event_log_window.borrow().as_ref() {
+                if event_log.window.is_visible() {
+                    event_log.refresh();
+                }
+            }
+        }));
+        self.imp().event_log_listener.set(Some(listener));
+    }
+
+    /// Failures in quick succession are counted in a single toast.
+    fn show_error_toast(&self, event: &LoggedEvent) {
+        let self_imp = self.imp();
+        let failed_calls = self_imp.failed_calls.get() + 1;
+        self_imp.failed_calls.set(failed_calls);
+        let title = if failed_calls == 1 {
+            format!("{} failed", event.method_name())
+        } else {
+            format!("{} daemon calls failed", failed_calls)
+        };
+        if let Some(toast) = self_imp.error_toast.borrow().as_ref() {
+            toast.set_title(&title);
+            return;
+        }
+        let toast = adw::Toast::builder()
+            .title(title)
+            .use_markup(false)
+            .button_label("Details")
+            .action_name("win.event-log")
+            .build();
+        toast.connect_dismissed(clone!(@weak self as window => move |_| {
+            window.imp().error_toast.take();
+            window.imp().failed_calls.set(0);
+        }));
+        self_imp.reset_toast_overlay.add_toast(toast.clone());
+        self_imp.error_toast.replace(Some(toast));
+    }
+
+    pub fn show_event_log(&self) {
+        let self_imp = self.imp();
+        let event_log = self_imp
+            .event_log_window
+            .borrow_mut()
+            .get_or_insert_with(|| EventLogWindow::new(self))
+            .clone();
+        event_log.present();
+    }
+
+    /// Shows a toast with an undo button.
+    /// The action only runs once the toast is dismissed without being undone,
+    /// or right away when the window is closed.
+    pub fn show_undo_toast(
+        &self,
+        title: &str,
+        undo: impl FnOnce() + 'static,
+        action: impl FnOnce() + 'static,
+    ) {
+        let self_imp = self.imp();
+        add_undo_toast(
+            &self_imp.reset_toast_overlay,
+            &self_imp.pending_actions,
+            title,
+            undo,
+            action,
+        );
+    }
+
+    pub fn show_banner(&self, message: &str) {
+        let self_imp = self.imp();
+        self_imp.reset_banner.set_title(message);
+        self_imp.reset_banner.set_revealed(true);
+    }
+
     pub fn toggle_sidebar(&self) {
         if self.imp().reset_overlay_split_view.shows_sidebar() {
             self.imp().reset_overlay_split_view.set_show_sidebar(false);
@@ -120,50 +465,189 @@ impl ReSetWindow {
     pub fn setup_sidebar_entries(&self) {
         let self_imp = self.imp();
         let capabilities = get_capabilities();
-        let wifi = capabilities.contains(&"WiFi".to_string());
-        let bluetooth = capabilities.contains(&"Bluetooth".to_string());
-        let audio = capabilities.contains(&"Audio".to_string());
-        self_imp.capabilities.set(wifi, bluetooth, audio);
+        let config = frontend_config();
 
-        let mut sidebar_list = Vec::new();
+        self.load_plugins(&capabilities, &config);
+        self.populate_sidebar(&capabilities);
+        self.watch_plugins(config.hot_reload_plugins);
 
-        if wifi || bluetooth {
-            sidebar_list.push(CONNECTIVITY_SIDEBAR);
-        }
-        if wifi {
-            sidebar_list.push(WIFI_SIDEBAR);
-        };
-        if bluetooth {
-            sidebar_list.push(BLUETOOTH_SIDEBAR);
-        };
-        if audio {
-            sidebar_list.push(AUDIO_SIDEBAR);
-            sidebar_list.push(SINK_SIDEBAR);
-            sidebar_list.push(SOURCE_SIDEBAR);
+        // the last page is skipped if it is no longer available, e.g. a removed plugin
+        let restored_position = self_imp
+            .restored_position
+            .take()
+            .filter(|position| self.can_open(position));
+        match config.start_page.or(restored_position) {
+            Some(position) => self.navigate_to(position),
+            None if self.find_sidebar_entry(&Position::Home).is_some() => {
+                self.navigate_to(Position::Home);
+            }
+            None if self_imp.compatibility.borrow().supports(SINK_METHODS)
+                && self.find_sidebar_entry(&Position::AudioOutput).is_some() =>
+            {
+                HANDLE_VOLUME_CLICK(
+                    &self_imp.capabilities,
+                    self_imp.listeners.clone(),
+                    self_imp.reset_main.clone(),
+                    self_imp.position.clone(),
+                );
+            }
+            None => {}
         }
 
-        let mut plugin_sidebar_list = vec![];
+        self_imp
+            .reset_sidebar_list
+            .connect_row_activated(clone!(@ weak self_imp => move |_, _| {
+                self_imp.reset_search_entry.set_text("");
+            }));
+
+        let window = SendWeakRef::from(self.downgrade());
+        watch_daemon_name(self_imp.listeners.clone(), move |event| {
+            if let Some(window) = window.upgrade() {
+                window.handle_daemon_event(event);
+            }
+        });
+    }
+
+    /// Starts the plugins enabled in the frontend config that are not loaded yet
+    /// and records the state of every plugin for the plugins page.
+    /// Plugins are only unloaded to reload them, disabled ones are just left out of the sidebar.
+    /// Plugins that panic or return unusable data are disabled for the session.
+    fn load_plugins(&self, capabilities: &[String], config: &FrontendConfig) {
+        let self_imp = self.imp();
+        let mut plugin_sidebar_list = self_imp.plugin_sidebar_entries.borrow_mut();
+        let mut plugin_infos = Vec::new();
+        let mut unusable_plugins = Vec::new();
+        set_host_window(self);
         unsafe {
             for plugin in FRONTEND_PLUGINS.iter() {
                 let plugin_capabilities = &plugin.capabilities;
-
-                (plugin.frontend_startup)();
-
-                let (sidebar_info, plugin_boxes) = (plugin.frontend_data)();
-                let listeners = self_imp.listeners.clone();
-
-                if plugin_capabilities.1 {
-                    let mut found = false;
-                    for capability in plugin_capabilities.0.iter() {
-                        if capabilities.contains(&capability.to_string()) {
-                            found = true;
-                            break;
+                let library = plugin_library(plugin);
+                let plugin_file = library
+                    .as_deref()
+                    .map(library_file_name)
+                    .unwrap_or_default();
+                let mut info = PluginInfo {
+                    file_name: plugin_file.clone(),
+                    name: None,
+                    capabilities: plugin_capabilities
+                        .0
+                        .iter()
+                        .map(|capability| capability.to_string())
+                        .collect(),
+                    requires_capabilities: plugin_capabilities.1,
+                    capabilities_satisfied: plugin_capabilities
+                        .0
+                        .iter()
+                        .any(|capability| capabilities.contains(&capability.to_string())),
+                    kind: None,
+                    status: PluginStatus::Disabled,
+                };
+                if let Some(status) = session_status(&plugin_file) {
+                    info.status = status;
+                    plugin_infos.push(info);
+                    continue;
+                }
+                // plugins disabled by the plugins page are skipped before any frontend function,
+                // re_set-lib already loaded them and called capabilities, see the plugins page
+                if config.plugin_denied(&plugin_file) {
+                    plugin_infos.push(info);
+                    continue;
+                }
+                // re_set-lib called capabilities while loading the library
+                let take_panic = library.as_deref().and_then(panic_taker);
+                if let Some(message) = take_panic.and_then(caught_panic) {
+                    info.status = PluginStatus::Failed(PluginError::new("capabilities", message));
+                    disable_for_session(&plugin_file, info.status.clone());
+                    unusable_plugins.push(plugin_file.clone());
+                    plugin_infos.push(info);
+                    continue;
+                }
+                // functions returning rust types may only be called with a matching ABI
+                let abi = match library.as_deref() {
+                    Some(path) => check_plugin_abi(path),
+                    None => Err(String::from("could not find the plugin library")),
+                };
+                if let Err(message) = abi {
+                    info.status = PluginStatus::Incompatible(message);
+                    disable_for_session(&plugin_file, info.status.clone());
+                    unusable_plugins.push(plugin_file.clone());
+                    plugin_infos.push(info);
+                    continue;
+                }
+                info.kind = library.as_deref().and_then(plugin_kind);
+                let plugin_name =
+                    match call_plugin("frontend_name", take_panic, || (plugin.frontend_name)()) {
+                        Ok(plugin_name) => plugin_name,
+                        Err(error) => {
+                            info.status = PluginStatus::Failed(error);
+                            disable_for_session(&plugin_file, info.status.clone());
+                            unusable_plugins.push(plugin_file.clone());
+                            plugin_infos.push(info);
+                            continue;
                         }
-                    }
-                    if !found {
+                    };
+                info.name = Some(plugin_name.clone());
+                if !config.plugin_enabled(&[&plugin_file, &plugin_name]) {
+                    plugin_infos.push(info);
+                    continue;
+                }
+                if plugin_capabilities.1 && !info.capabilities_satisfied {
+                    info.status = PluginStatus::MissingCapabilities;
+                    plugin_infos.push(info);
+                    continue;
+                }
+                if plugin_sidebar_list
+                    .iter()
+                    .any(|entry| entry.plugin_file == plugin_file)
+                {
+                    info.status = PluginStatus::Loaded;
+                    plugin_infos.push(info);
+                    continue;
+                }
+
+                let mut taken_names = BUILTIN_SIDEBAR_NAMES.to_vec();
+                taken_names.extend(plugin_sidebar_list.iter().map(|entry| entry.name));
+                let data = library
+                    .as_deref()
+                    .map_or(Ok(()), provide_host)
+                    .and_then(|_| {
+                        call_plugin("frontend_startup", take_panic, || {
+                            (plugin.frontend_startup)()
+                        })
+                    })
+                    .and_then(|_| {
+                        call_plugin("frontend_data", take_panic, || (plugin.frontend_data)())
+                    })
+                    .and_then(|(sidebar_info, plugin_boxes)| {
+                        validate_plugin_data(&sidebar_info, &plugin_boxes, &taken_names)?;
+                        Ok((sidebar_info, plugin_boxes))
+                    })
+                    .and_then(|(sidebar_info, plugin_boxes)| {
+                        let search_keywords = match library.as_deref() {
+                            Some(path) => {
+                                register_extensions(&plugin_file, plugin_extensions(path)?)?;
+                                plugin_search_keywords(path)?
+                            }
+                            None => Vec::new(),
+                        };
+                        Ok((sidebar_info, plugin_boxes, search_keywords))
+                    });
+                let (sidebar_info, plugin_boxes, search_keywords) = match data {
+                    Ok(data) => data,
+                    Err(error) => {
+                        info.status = PluginStatus::Failed(error);
+                        disable_for_session(&plugin_file, info.status.clone());
+                        unusable_plugins.push(plugin_file.clone());
+                        plugin_infos.push(info);
                         continue;
                     }
-                }
+                };
+                info.status = PluginStatus::Loaded;
+                plugin_infos.push(info);
+                mark_started(&plugin_file);
+
+                let hooks = library.as_deref().map(plugin_hooks).unwrap_or_default();
+                let listeners = self_imp.listeners.clone();
                 let event = Rc::new(
                     move |reset_main: FlowBox,
                           position: Rc<RefCell<Position>>,
@@ -182,6 +666,7 @@ impl ReSetWindow {
                             reset_main.insert(&frame, -1);
                         }
                         reset_main.set_max_children_per_line(boxes.len() as u32);
+                        listeners.enter_plugin_page(hooks);
                     },
                 );
 
@@ -191,42 +676,408 @@ impl ReSetWindow {
                     parent: sidebar_info.parent,
                     click_event: event,
                     plugin_boxes,
+                    plugin_name,
+                    plugin_file,
+                    search_keywords,
                 });
             }
         }
+        self.load_manifest_plugins(
+            capabilities,
+            config,
+            &mut plugin_sidebar_list,
+            &mut plugin_infos,
+            &mut unusable_plugins,
+        );
+        set_enabled_extension_plugins(
+            plugin_infos
+                .iter()
+                .filter(|info| info.status == PluginStatus::Loaded)
+                .map(|info| info.file_name.clone())
+                .collect(),
+        );
+        set_plugin_infos(plugin_infos);
+
+        if !unusable_plugins.is_empty() {
+            // file names, as plugins that failed early have no name
+            self.show_banner(&format!(
+                "Plugins disabled until the next start, see the plugins page: {}",
+                unusable_plugins.join(", ")
+            ));
+        }
+    }
 
-        HANDLE_VOLUME_CLICK(
-            &self_imp.capabilities,
-            self_imp.listeners.clone(),
-            self_imp.reset_main.clone(),
-            self_imp.position.clone(),
+    /// Adds the plugins described by manifests in the plugin directory,
+    /// following the same rules as the plugin libraries.
+    fn load_manifest_plugins(
+        &self,
+        capabilities: &[String],
+        config: &FrontendConfig,
+        plugin_sidebar_list: &mut Vec<PluginSidebarInfo>,
+        plugin_infos: &mut Vec<PluginInfo>,
+        unusable_plugins: &mut Vec<String>,
+    ) {
+        for (plugin_file, manifest) in read_manifests() {
+            let mut info = PluginInfo {
+                file_name: plugin_file.clone(),
+                name: None,
+                capabilities: Vec::new(),
+                requires_capabilities: false,
+                capabilities_satisfied: true,
+                kind: Some(PluginKind::Manifest),
+                status: PluginStatus::Disabled,
+            };
+            if let Some(status) = session_status(&plugin_file) {
+                info.status = status;
+                plugin_infos.push(info);
+                continue;
+            }
+            let manifest = match manifest {
+                Ok(manifest) => manifest,
+                Err(message) => {
+                    info.status = PluginStatus::Failed(PluginError::new("manifest", message));
+                    disable_for_session(&plugin_file, info.status.clone());
+                    unusable_plugins.push(plugin_file);
+                    plugin_infos.push(info);
+                    continue;
+                }
+            };
+            info.name = Some(manifest.name.clone());
+            info.requires_capabilities = !manifest.capabilities.is_empty();
+            info.capabilities_satisfied = manifest
+                .capabilities
+                .iter()
+                .all(|capability| capabilities.contains(capability));
+            info.capabilities = manifest.capabilities.clone();
+            if !config.plugin_enabled(&[&plugin_file, &manifest.name]) {
+                plugin_infos.push(info);
+                continue;
+            }
+            if !info.capabilities_satisfied {
+                info.status = PluginStatus::MissingCapabilities;
+                plugin_infos.push(info);
+                continue;
+            }
+            if plugin_sidebar_list
+                .iter()
+                .any(|entry| entry.plugin_file == plugin_file)
+            {
+                info.status = PluginStatus::Loaded;
+                plugin_infos.push(info);
+                continue;
+            }
+            let mut taken_names = BUILTIN_SIDEBAR_NAMES.to_vec();
+            taken_names.extend(plugin_sidebar_list.iter().map(|entry| entry.name));
+            if taken_names.contains(&manifest.name.as_str()) {
+                info.status = PluginStatus::Failed(PluginError::new(
+                    "manifest",
+                    format!("sidebar name {} is already in use", manifest.name),
+                ));
+                disable_for_session(&plugin_file, info.status.clone());
+                unusable_plugins.push(plugin_file.clone());
+                plugin_infos.push(info);
+                continue;
+            }
+            info.status = PluginStatus::Loaded;
+            plugin_infos.push(info);
+
+            // sidebar entries live for the rest of the session, manifests are only loaded once
+            let name: &'static str = Box::leak(manifest.name.clone().into_boxed_str());
+            let mut search_keywords: Vec<SearchKeyword> = manifest
+                .keywords
+                .iter()
+                .map(|keyword| SearchKeyword::new(keyword, None))
+                .collect();
+            search_keywords.extend(
+                manifest
+                    .rows
+                    .iter()
+                    .map(|row| SearchKeyword::row(&row.title)),
+            );
+            let (page, rows) = create_manifest_page(&manifest);
+            let listeners = self.imp().listeners.clone();
+            let event = Rc::new(
+                move |reset_main: FlowBox,
+                      position: Rc<RefCell<Position>>,
+                      boxes: Vec<gtk::Box>| {
+                    if handle_init(
+                        listeners.clone(),
+                        position,
+                        Position::Custom(String::from(name)),
+                    ) {
+                        return;
+                    }
+                    reset_main.remove_all();
+                    for plugin_box in &boxes {
+                        let frame = wrap_in_flow_box_child(SettingBox::new(&plugin_box.clone()));
+                        reset_main.insert(&frame, -1);
+                    }
+                    reset_main.set_max_children_per_line(1);
+                    refresh_rows(&rows);
+                    listen_for_signals(&listeners, &rows);
+                },
+            );
+            plugin_sidebar_list.push(PluginSidebarInfo {
+                name,
+                icon_name: Box::leak(manifest.icon_name.into_boxed_str()),
+                parent: manifest
+                    .parent
+                    .map(|parent| &*Box::leak(parent.into_boxed_str())),
+                click_event: event,
+                plugin_boxes: vec![page],
+                plugin_name: manifest.name,
+                plugin_file,
+                search_keywords,
+            });
+        }
+    }
+
+    /// Fills the sidebar with the built-in pages available for the given daemon capabilities,
+    /// followed by the already loaded plugin pages.
+    /// Entries hidden or plugins disabled in the frontend config are left out.
+    fn populate_sidebar(&self, capabilities: &[String]) {
+        let self_imp = self.imp();
+        let config = frontend_config();
+        let hidden = |name: &str, parent: Option<&str>| {
+            config.is_hidden(name) || parent.is_some_and(|parent| config.is_hidden(parent))
+        };
+        self_imp.daemon_capabilities.replace(capabilities.to_vec());
+        set_extension_capabilities(capabilities);
+        let wifi = capabilities.contains(&"WiFi".to_string());
+        let bluetooth = capabilities.contains(&"Bluetooth".to_string());
+        let audio = capabilities.contains(&"Audio".to_string());
+        let compatibility = self_imp.compatibility.borrow().clone();
+        if let Some(message) = compatibility.mismatch_message() {
+            self_imp.reset_banner.set_title(&message);
+            self_imp.reset_banner.set_revealed(true);
+        }
+        // boxes on the connectivity page are only shown when their methods are available
+        self_imp.capabilities.set(
+            wifi && compatibility.supports(WIFI_METHODS),
+            bluetooth && compatibility.supports(BLUETOOTH_METHODS),
+            audio,
         );
 
-        self_imp
-            .reset_sidebar_list
-            .connect_row_activated(clone!(@ weak self_imp => move |_, _| {
-                self_imp.reset_search_entry.set_text("");
-            }));
+        let mut sidebar_list = vec![HOME_SIDEBAR];
+
+        if wifi || bluetooth {
+            sidebar_list.push(CONNECTIVITY_SIDEBAR);
+        }
+        if wifi {
+            sidebar_list.push(WIFI_SIDEBAR);
+        };
+        if bluetooth {
+            sidebar_list.push(BLUETOOTH_SIDEBAR);
+        };
+        if audio {
+            sidebar_list.push(AUDIO_SIDEBAR);
+            sidebar_list.push(SINK_SIDEBAR);
+            sidebar_list.push(SOURCE_SIDEBAR);
+        }
+
+        self_imp.reset_sidebar_list.remove_all();
         let mut i = 0;
         for info in sidebar_list {
+            if hidden(info.name, info.parent) {
+                continue;
+            }
             if info.parent.is_none() && i != 0 {
                 self_imp.reset_sidebar_list.insert(&create_separator(), i);
                 i += 1;
             }
             let entry = SidebarEntry::new(&info);
+            if !compatibility.supports(info.required_methods) {
+                entry.set_sensitive(false);
+                entry.set_tooltip_text(Some("Not supported by the running daemon"));
+            }
             self_imp.reset_sidebar_list.insert(&entry, i);
             i += 1;
         }
 
-        for info in plugin_sidebar_list {
+        for info in self_imp.plugin_sidebar_entries.borrow().iter() {
+            if !config.plugin_enabled(&[&info.plugin_file, &info.plugin_name])
+                || hidden(info.name, info.parent)
+            {
+                continue;
+            }
             if info.parent.is_none() && i != 0 {
                 self_imp.reset_sidebar_list.insert(&create_separator(), i);
                 i += 1;
             }
-            let entry = SidebarEntry::new(&info);
+            let entry = SidebarEntry::new(info);
             self_imp.reset_sidebar_list.insert(&entry, i);
             i += 1;
         }
+
+        // only shown if there is anything to manage
+        if !plugin_infos().is_empty() && !hidden(PLUGINS_SIDEBAR.name, None) {
+            if i != 0 {
+                self_imp.reset_sidebar_list.insert(&create_separator(), i);
+                i += 1;
+            }
+            let entry = SidebarEntry::new(&PLUGINS_SIDEBAR);
+            self_imp.reset_sidebar_list.insert(&entry, i);
+        }
+    }
+
+    fn handle_daemon_event(&self, event: DaemonEvent) {
+        let self_imp = self.imp();
+        match event {
+            DaemonEvent::Vanished => {
+                self_imp.listeners.stop_network_listener();
+                self_imp.listeners.stop_audio_listener();
+                self_imp.listeners.stop_bluetooth_listener();
+                self_imp
+                    .reset_banner
+                    .set_title("Lost connection to the daemon, waiting for it to return");
+                self_imp.reset_banner.set_revealed(true);
+            }
+            DaemonEvent::Appeared => {
+                let window = self.clone();
+                glib::spawn_future_local(async move {
+                    let state = gio::spawn_blocking(|| {
+                        if register_client().is_err() {
+                            return None;
+                        }
+                        Some((get_capabilities(), DaemonCompatibility::query()))
+                    })
+                    .await
+                    .unwrap_or(None);
+                    if let Some((capabilities, compatibility)) = state {
+                        window.reconnect(capabilities, compatibility);
+                    } else {
+                        ERROR!(
+                            "Could not register at the restarted daemon",
+                            ErrorLevel::Critical
+                        );
+                    }
+                });
+            }
+        }
+    }
+
+    /// Rebuilds the sidebar and the current page after the daemon restarted,
+    /// which also restarts the listeners of the page.
+    fn reconnect(&self, capabilities: Vec<String>, compatibility: DaemonCompatibility) {
+        let self_imp = self.imp();
+        self_imp.reset_banner.set_revealed(false);
+        self_imp.compatibility.replace(compatibility);
+        self.populate_sidebar(&capabilities);
+        self.reload_page();
+    }
+
+    fn watch_config(&self) {
+        let window = self.downgrade();
+        let monitor = watch_frontend_config(move |old_config, new_config| {
+            if let Some(window) = window.upgrade() {
+                window.apply_config(old_config, new_config);
+            }
+        });
+        self.imp().config_monitor.replace(monitor);
+    }
+
+    /// Reloads plugins whenever their library is rebuilt while enabled.
+    fn watch_plugins(&self, enabled: bool) {
+        let monitors = if enabled {
+            let window = self.downgrade();
+            watch_plugin_libraries(move |plugin_file, source| {
+                if let Some(window) = window.upgrade() {
+                    window.reload_plugin(plugin_file, source);
+                }
+            })
+        } else {
+            Vec::new()
+        };
+        self.imp().plugin_monitors.replace(monitors);
+    }
+
+    /// Replaces a plugin with a new build of its library and shows its page again if it was open.
+    fn reload_plugin(&self, plugin_file: &str, source: &Path) {
+        let self_imp = self.imp();
+        let shown_position = self.unload_plugin(plugin_file);
+        if let Err(error) = replace_plugin(unsafe { &mut FRONTEND_PLUGINS }, plugin_file, source) {
+            ERROR!(
+                format!("Could not reload plugin {}: {}", plugin_file, error),
+                ErrorLevel::Recoverable
+            );
+            self.show_banner(&format!(
+                "Could not reload plugin {}: {}",
+                plugin_file, error
+            ));
+            disable_for_session(
+                plugin_file,
+                PluginStatus::Failed(PluginError::new("reload", error)),
+            );
+        }
+        let capabilities = self_imp.daemon_capabilities.borrow().clone();
+        self.load_plugins(&capabilities, &frontend_config());
+        self.populate_sidebar(&capabilities);
+        if let Some(position) = shown_position {
+            if self.find_sidebar_entry(&position).is_some() {
+                self.navigate_to(position);
+            }
+        }
+    }
+
+    /// Shuts a plugin down and removes its sidebar entry, page and extensions,
+    /// so that it can be loaded again. Returns the position of its page if it was shown.
+    fn unload_plugin(&self, plugin_file: &str) -> Option<Position> {
+        let self_imp = self.imp();
+        let removed = {
+            let mut plugin_sidebar_list = self_imp.plugin_sidebar_entries.borrow_mut();
+            plugin_sidebar_list
+                .iter()
+                .position(|entry| entry.plugin_file == plugin_file)
+                .map(|index| plugin_sidebar_list.remove(index))
+        };
+        let mut shown_position = None;
+        if let Some(entry) = removed {
+            let position = Position::Custom(String::from(entry.name));
+            if *self_imp.position.borrow() == position {
+                self_imp.listeners.leave_plugin_page();
+                self_imp.reset_main.remove_all();
+                self_imp.position.replace(Position::Home);
+                shown_position = Some(position);
+            }
+            // the old widgets must not be reused by the new build
+            for plugin_box in &entry.plugin_boxes {
+                plugin_box.unparent();
+            }
+        }
+        unregister_extensions(plugin_file);
+        shutdown_plugin(plugin_file);
+        enable_for_session(plugin_file);
+        shown_position
+    }
+
+    /// Applies an edited frontend config to the running window.
+    fn apply_config(&self, old_config: &FrontendConfig, new_config: &FrontendConfig) {
+        let self_imp = self.imp();
+        if old_config.window_size != new_config.window_size {
+            if let Some((width, height)) = new_config.window_size {
+                self.set_default_size(width, height);
+            }
+        }
+        // the sidebar is only set up once the daemon is available
+        if self_imp.daemon_capabilities.borrow().is_empty() {
+            return;
+        }
+        if old_config.hot_reload_plugins != new_config.hot_reload_plugins {
+            self.watch_plugins(new_config.hot_reload_plugins);
+        }
+        if old_config.hidden_entries != new_config.hidden_entries
+            || old_config.allowed_plugins != new_config.allowed_plugins
+            || old_config.denied_plugins != new_config.denied_plugins
+        {
+            let capabilities = self_imp.daemon_capabilities.borrow().clone();
+            self.load_plugins(&capabilities, new_config);
+            self.populate_sidebar(&capabilities);
+            self.reload_page();
+        } else if old_config.volume_step != new_config.volume_step
+            || old_config.max_volume != new_config.max_volume
+        {
+            self.reload_page();
+        }
     }
 
     pub fn setup_shortcuts(&self) {
@@ -251,6 +1102,57 @@ impl ReSetWindow {
             })
             .build();
 
+        let navigate_action = ActionEntry::builder("navigate")
+            .parameter_type(Some(&String::static_variant_type()))
+            .activate(move |window: &Self, _, page| {
+                let position = page
+                    .and_then(|page| page.get::<String>())
+                    .and_then(|page| Position::from_page_name(&page));
+                if let Some(position) = position {
+                    window.navigate_to(position);
+                }
+            })
+            .build();
+
+        let open_plugin_action = ActionEntry::builder("open-plugin")
+            .parameter_type(Some(&String::static_variant_type()))
+            .activate(move |window: &Self, _, plugin_file| {
+                let plugin_file = match plugin_file.and_then(|file| file.get::<String>()) {
+                    Some(plugin_file) => plugin_file,
+                    None => return,
+                };
+                let name = window
+                    .imp()
+                    .plugin_sidebar_entries
+                    .borrow()
+                    .iter()
+                    .find(|entry| entry.plugin_file == plugin_file)
+                    .map(|entry| entry.name);
+                // plugins without a page only contribute the tile
+                if let Some(name) = name {
+                    window.navigate_to(Position::Custom(String::from(name)));
+                }
+            })
+            .build();
+
+        let back_action = ActionEntry::builder("back")
+            .activate(move |window: &Self, _, _| {
+                window.go_back();
+            })
+            .build();
+
+        let forward_action = ActionEntry::builder("forward")
+            .activate(move |window: &Self, _, _| {
+                window.go_forward();
+            })
+            .build();
+
+        let event_log_action = ActionEntry::builder("event-log")
+            .activate(move |window: &Self, _, _| {
+                window.show_event_log();
+            })
+            .build();
+
         let close_action = ActionEntry::builder("close")
             .activate(move |window: &Self, _, _| {
                 window.close();
@@ -331,6 +1233,11 @@ impl ReSetWindow {
         self.add_action_entries([
             search_action,
             banner_action,
+            navigate_action,
+            open_plugin_action,
+            back_action,
+            forward_action,
+            event_log_action,
             close_action,
             about_action,
             vim_up,
@@ -340,6 +1247,21 @@ impl ReSetWindow {
             error_popup_action,
             error_popdown_action,
         ]);
+        self.update_history_actions();
+
+        // the back and forward buttons of a mouse
+        let mouse_buttons = GestureClick::builder()
+            .button(0)
+            .propagation_phase(PropagationPhase::Capture)
+            .build();
+        mouse_buttons.connect_pressed(clone!(@weak self as window => move |gesture, _, _, _| {
+            match gesture.current_button() {
+                8 => window.go_back(),
+                9 => window.go_forward(),
+                _ => {}
+            }
+        }));
+        self.add_controller(mouse_buttons);
     }
 }
 
@@ -391,12 +1313,41 @@ fn setup_callback(window: Rc<ReSetWindow>) -> Rc<ReSetWindow> {
                     result.imp().plugin_boxes.borrow().clone(),
                 );
             }
+            activated_ref.record_navigation();
+            // entries found through a keyword lead to the row the keyword refers to
+            let text = imp.reset_search_entry.text();
+            let row = matching_row(
+                &result.imp().name.borrow(),
+                &result.imp().search_keywords.borrow(),
+                &text,
+            )
+            .map(String::from);
+            if let Some(row) = row {
+                highlight_row(&imp.reset_main.get(), row);
+            }
         });
 
     self_imp.reset_close.connect_clicked(move |_| {
         close_ref.close();
     });
 
+    window.connect_close_request(|window| {
+        run_pending_actions(&window.imp().pending_actions);
+        record_window_state(window.window_state());
+        window.imp().listeners.leave_plugin_page();
+        if let Some(listener) = window.imp().event_log_listener.take() {
+            disconnect_event_logged(listener);
+        }
+        glib::Propagation::Proceed
+    });
+    window.connect_destroy(|window| {
+        window
+            .imp()
+            .listeners
+            .daemon_watcher
+            .store(false, Ordering::SeqCst);
+    });
+
     self_imp.reset_banner.connect_button_clicked(|banner| {
         banner.set_revealed(false);
         banner.set_title("Info");
@@ -405,6 +1356,83 @@ fn setup_callback(window: Rc<ReSetWindow>) -> Rc<ReSetWindow> {
     window
 }
 
+/// Shows an undo toast in the window of the widget, either the main window or the quick settings.
+/// Without a window to show it in, the action runs right away.
+pub fn show_undo_toast(
+    widget: &impl IsA<gtk::Widget>,
+    title: &str,
+    undo: impl FnOnce() + 'static,
+    action: impl FnOnce() + 'static,
+) {
+    let root = widget.root();
+    if let Some(window) = root
+        .as_ref()
+        .and_then(|root| root.downcast_ref::<ReSetWindow>())
+    {
+        window.show_undo_toast(title, undo, action);
+        return;
+    }
+    match root.as_ref().and_then(quick_settings_toasts) {
+        Some((overlay, pending_actions)) => {
+            add_undo_toast(&overlay, &pending_actions, title, undo, action)
+        }
+        None => action(),
+    }
+}
+
+/// Shows a toast whose action runs once it is dismissed, unless it was undone.
+/// The action is kept in `pending_actions` until then, so that closing the window can run it early.
+pub fn add_undo_toast(
+    overlay: &ToastOverlay,
+    pending_actions: &PendingActions,
+    title: &str,
+    undo: impl FnOnce() + 'static,
+    action: impl FnOnce() + 'static,
+) {
+    let action: Box<dyn FnOnce()> = Box::new(action);
+    let pending: PendingAction = Rc::new(RefCell::new(Some(action)));
+    let toast = adw::Toast::builder()
+        .title(title)
+        .use_markup(false)
+        .button_label("Undo")
+        .build();
+    let undo = RefCell::new(Some(undo));
+    toast.connect_button_clicked(clone!(@strong pending => move |_| {
+        pending.take();
+        if let Some(undo) = undo.take() {
+            undo();
+        }
+    }));
+    // the toast is kept in the pending actions, a strong reference would never be dropped
+    let weak_pending_actions = Rc::downgrade(pending_actions);
+    toast.connect_dismissed(clone!(@strong pending => move |toast| {
+        if let Some(pending_actions) = weak_pending_actions.upgrade() {
+            pending_actions
+                .borrow_mut()
+                .retain(|(pending_toast, _)| pending_toast != toast);
+        }
+        // taken before running, the action may show another toast
+        let action = pending.take();
+        if let Some(action) = action {
+            action();
+        }
+    }));
+    pending_actions.borrow_mut().push((toast.clone(), pending));
+    overlay.add_toast(toast);
+}
+
+/// Runs the actions of all undo toasts, they can no longer be undone.
+pub fn run_pending_actions(pending_actions: &PendingActions) {
+    let pending_actions = pending_actions.take();
+    for (toast, pending) in pending_actions {
+        let action = pending.take();
+        if let Some(action) = action {
+            action();
+        }
+        toast.dismiss();
+    }
+}
+
 pub fn create_separator() -> ListBoxRow {
     let separator: gtk::Separator = gtk::Separator::builder()
         .margin_bottom(3)
@@ -437,6 +1465,7 @@ fn handle_init(
     listeners.stop_network_listener();
     listeners.stop_audio_listener();
     listeners.stop_bluetooth_listener();
+    listeners.leave_plugin_page();
     false
 }
 
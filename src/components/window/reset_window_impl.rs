@@ -1,23 +1,32 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 
 use adw::subclass::prelude::AdwApplicationWindowImpl;
-use adw::{Breakpoint, OverlaySplitView};
+use adw::{Breakpoint, NavigationView, OverlaySplitView, Toast, ToastOverlay};
 use glib::prelude::StaticTypeExt;
 use glib::subclass::InitializingObject;
 use gtk::prelude::WidgetExt;
 use gtk::subclass::prelude::*;
-use gtk::{Button, CompositeTemplate, FlowBox, ListBox, SearchEntry};
+use gtk::{gio, Button, CompositeTemplate, FlowBox, ListBox, SearchEntry};
 
 use crate::components::base::error::ReSetError;
+use crate::components::base::event_log::EventListenerId;
 use crate::components::base::utils::{Listeners, Position};
+use crate::components::daemon::compat::DaemonCompatibility;
+use crate::components::plugin::function::PluginSidebarInfo;
 use crate::components::utils::Capabilities;
 use crate::components::wifi::wifi_box::WifiBox;
+use crate::components::window::event_log_window::EventLogWindow;
+use crate::components::window::history::NavigationHistory;
 use crate::components::window::reset_window;
 use crate::components::window::sidebar_entry::SidebarEntry;
 
 type SidebarEntries = RefCell<Vec<(Rc<SidebarEntry>, Vec<Rc<SidebarEntry>>)>>;
+/// Runs the action behind an undo toast, taken once it is either run or undone.
+pub type PendingAction = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+/// Undo toasts whose action has not run yet, shared with the callbacks of the toasts.
+pub type PendingActions = Rc<RefCell<Vec<(Toast, PendingAction)>>>;
 
 #[derive(CompositeTemplate, Default)]
 #[template(resource = "/org/Xetibo/ReSet/resetMainWindow.ui")]
@@ -38,6 +47,8 @@ pub struct ReSetWindow {
     pub reset_sidebar_toggle: TemplateChild<Button>,
     #[template_child]
     pub reset_close: TemplateChild<Button>,
+    #[template_child]
+    pub reset_toast_overlay: TemplateChild<ToastOverlay>,
     // #[template_child]
     // pub reset_banner: TemplateChild<adw::Banner>,
     pub sidebar_entries: SidebarEntries,
@@ -46,6 +57,25 @@ pub struct ReSetWindow {
     pub position: Rc<RefCell<Position>>,
     pub error_popup: ReSetError,
     pub capabilities: Capabilities, 
+    pub compatibility: RefCell<DaemonCompatibility>,
+    pub plugin_sidebar_entries: RefCell<Vec<PluginSidebarInfo>>,
+    pub daemon_capabilities: RefCell<Vec<String>>,
+    pub config_monitor: RefCell<Option<gio::FileMonitor>>,
+    pub plugin_monitors: RefCell<Vec<gio::FileMonitor>>,
+    pub restored_position: RefCell<Option<Position>>,
+    pub history: RefCell<NavigationHistory>,
+    /// Set while a history entry is restored, so that restoring it is not recorded again.
+    pub restoring_history: Cell<bool>,
+    /// Navigation views of the current page whose sub pages are recorded in the history.
+    pub navigation_views: RefCell<Vec<NavigationView>>,
+    /// Toast of the failed daemon calls, updated while it is shown.
+    pub error_toast: RefCell<Option<Toast>>,
+    pub failed_calls: Cell<u32>,
+    pub event_log_window: RefCell<Option<EventLogWindow>>,
+    /// Removed when the window is closed, the event log outlives it.
+    pub event_log_listener: Cell<Option<EventListenerId>>,
+    /// Actions waiting for their undo toast to time out.
+    pub pending_actions: PendingActions,
 }
 
 unsafe impl Send for ReSetWindow {}
@@ -75,7 +105,6 @@ impl ObjectImpl for ReSetWindow {
         let obj = self.obj();
         obj.setup_shortcuts();
         obj.handle_dynamic_sidebar();
-        obj.setup_sidebar_entries();
     }
 }
 
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use adw::prelude::{PreferencesGroupExt, PreferencesRowExt};
+use gtk::prelude::*;
+use gtk::{graphene, ScrolledWindow, Viewport};
+
+use crate::components::plugin::search::SearchKeyword;
+
+const HIGHLIGHT_CLASS: &str = "resetSearchMatch";
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(2);
+// pages fill in their rows after their sidebar entry is clicked
+const HIGHLIGHT_DELAY: Duration = Duration::from_millis(200);
+
+/// Whether a sidebar entry is found by the search text, either by its name or by a keyword.
+pub fn entry_matches(name: &str, keywords: &[SearchKeyword], text: &str) -> bool {
+    let text = text.to_lowercase();
+    name.to_lowercase().contains(&text)
+        || keywords
+            .iter()
+            .any(|keyword| keyword.keyword.to_lowercase().contains(&text))
+}
+
+/// The row to highlight after opening a page through the search,
+/// pages found by their name are shown as usual.
+pub fn matching_row<'a>(name: &str, keywords: &'a [SearchKeyword], text: &str) -> Option<&'a str> {
+    let text = text.to_lowercase();
+    if text.is_empty() || name.to_lowercase().contains(&text) {
+        return None;
+    }
+    keywords
+        .iter()
+        .filter(|keyword| keyword.keyword.to_lowercase().contains(&text))
+        .find_map(|keyword| keyword.row.as_deref())
+}
+
+/// Scrolls to the row with the given title once the page is shown and highlights it briefly.
+pub fn highlight_row(page: &impl IsA<gtk::Widget>, title: String) {
+    let page = page.as_ref().downgrade();
+    glib::timeout_add_local_once(HIGHLIGHT_DELAY, move || {
+        let row = match page.upgrade().and_then(|page| find_row(&page, &title)) {
+            Some(row) => row,
+            None => return,
+        };
+        scroll_to(&row);
+        row.grab_focus();
+        row.add_css_class(HIGHLIGHT_CLASS);
+        glib::timeout_add_local_once(HIGHLIGHT_DURATION, move || {
+            row.remove_css_class(HIGHLIGHT_CLASS);
+        });
+    });
+}
+
+/// Finds the first shown row, group or label with the given title.
+fn find_row(widget: &gtk::Widget, title: &str) -> Option<gtk::Widget> {
+    // hidden pages of stacks are not mapped
+    if !widget.is_mapped() {
+        return None;
+    }
+    let widget_title = if let Some(row) = widget.downcast_ref::<adw::PreferencesRow>() {
+        Some(row.title())
+    } else if let Some(group) = widget.downcast_ref::<adw::PreferencesGroup>() {
+        Some(group.title())
+    } else {
+        widget
+            .downcast_ref::<gtk::Label>()
+            .map(|label| label.label())
+    };
+    if widget_title.is_some_and(|widget_title| widget_title.eq_ignore_ascii_case(title)) {
+        return Some(widget.clone());
+    }
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        if let Some(row) = find_row(&current, title) {
+            return Some(row);
+        }
+        child = current.next_sibling();
+    }
+    None
+}
+
+fn scroll_to(widget: &gtk::Widget) {
+    let scrolled_window = match widget
+        .ancestor(ScrolledWindow::static_type())
+        .and_downcast::<ScrolledWindow>()
+    {
+        Some(scrolled_window) => scrolled_window,
+        None => return,
+    };
+    let content = match scrolled_window.child() {
+        Some(child) => child
+            .downcast_ref::<Viewport>()
+            .and_then(|viewport| viewport.child())
+            .unwrap_or(child),
+        None => return,
+    };
+    if let Some(point) = widget.compute_point(&content, &graphene::Point::new(0.0, 0.0)) {
+        scrolled_window.vadjustment().set_value(point.y() as f64);
+    }
+}
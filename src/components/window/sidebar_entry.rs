@@ -22,6 +22,7 @@ impl SidebarEntry {
         if let Some(boxes) = info.plugin_boxes() {
             entry_imp.plugin_boxes.borrow_mut().extend(boxes);
         }
+        entry_imp.search_keywords.replace(info.search_keywords());
 
         match &info.parent() {
             None => {}
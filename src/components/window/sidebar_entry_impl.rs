@@ -6,6 +6,7 @@ use gtk::subclass::prelude::*;
 use gtk::{CompositeTemplate, Image, Label, ListBoxRow};
 
 use crate::components::plugin::function::{PluginClickEvent, RegularClickEvent};
+use crate::components::plugin::search::SearchKeyword;
 use crate::components::window::handle_sidebar_click::HANDLE_HOME;
 use crate::components::window::sidebar_entry;
 
@@ -30,6 +31,7 @@ pub struct SidebarEntry {
     pub on_click_event: RefCell<SidebarAction>,
     pub plugin_boxes: RefCell<Vec<gtk::Box>>,
     pub name: RefCell<String>,
+    pub search_keywords: RefCell<Vec<SearchKeyword>>,
 }
 
 pub struct SidebarAction {
@@ -1,18 +1,28 @@
-use std::hint::{self};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
 
-use components::utils::{BASE, DBUS_PATH};
+use cli::commands::{is_daemon_command, run_daemon_command};
+use cli::page::{is_quick_argument, parse_page_argument};
+use cli::plugin_tests::{
+    is_plugin_abi_check_command, is_plugin_test_command, run_plugin_abi_check, run_plugin_tests,
+};
+use components::config::state::{load_window_state, save_window_state};
+use components::daemon::compat::DaemonCompatibility;
+use components::daemon::readiness::{
+    daemon_timeout, mark_daemon_ready, register_client, unregister_client, wait_for_daemon,
+    watch_embedded_daemon,
+};
+use components::plugin::manager::shutdown_plugins;
+use components::plugin::reload::{reload_directory, remove_reload_copies};
+use components::window::quick_settings::show_quick_settings;
 use components::window::reset_window::ReSetWindow;
-use dbus::blocking::Connection;
-use dbus::Error;
 use gtk::gdk::Display;
 use gtk::prelude::*;
-use gtk::{gio, Application, CssProvider};
+use gtk::{gio, Application, ApplicationCommandLine, CssProvider};
 use reset_daemon::run_daemon;
 
+mod cli;
 mod components;
 mod tests;
 
@@ -24,24 +34,84 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[tokio::main]
 async fn main() {
+    let arguments: Vec<String> = std::env::args().collect();
+    // runs before anything else, the process only exists to load a single plugin
+    if is_plugin_abi_check_command(&arguments) {
+        if let Err(error) = run_plugin_abi_check(&arguments) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let ready = Arc::new(AtomicBool::new(false));
     tokio::task::spawn(daemon_check(ready.clone()));
-    while !ready.load(std::sync::atomic::Ordering::SeqCst) {
-        hint::spin_loop();
+    let daemon_available = wait_for_daemon(&ready, daemon_timeout()).await;
+    let compatibility = if daemon_available {
+        DaemonCompatibility::query()
+    } else {
+        DaemonCompatibility::default()
+    };
+
+    if is_plugin_test_command(&arguments) {
+        if !daemon_available {
+            eprintln!("ReSet daemon unavailable");
+            std::process::exit(2);
+        }
+        let res = run_plugin_tests(&arguments);
+        let _ = unregister_client();
+        match res {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(1),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(2);
+            }
+        }
+    }
+    if is_daemon_command(&arguments) {
+        if !daemon_available {
+            eprintln!("ReSet daemon unavailable");
+            std::process::exit(1);
+        }
+        if let Some(message) = compatibility.mismatch_message() {
+            eprintln!("Warning: {}", message);
+        }
+        let res = run_daemon_command(&arguments);
+        let _ = unregister_client();
+        if let Err(error) = res {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
     }
+
     gio::resources_register_include!("src.templates.gresource")
         .expect("Failed to register resources.");
     gio::resources_register_include!("src.icons.gresource").expect("Failed to register resources.");
     gio::resources_register_include!("src.style.gresource").expect("Failed to register resources.");
 
-    let app = Application::builder().application_id(APP_ID).build();
+    let app = Application::builder()
+        .application_id(APP_ID)
+        .flags(gio::ApplicationFlags::HANDLES_COMMAND_LINE)
+        .build();
 
     app.connect_startup(move |_| {
         adw::init().unwrap();
         load_css();
     });
 
-    app.connect_activate(build_ui);
+    let command_line_ready = ready.clone();
+    let command_line_compatibility = compatibility.clone();
+    app.connect_command_line(move |app, command_line| {
+        handle_command_line(
+            app,
+            command_line,
+            &command_line_ready,
+            &command_line_compatibility,
+        )
+    });
+    app.connect_activate(move |app| build_ui(app, ready.clone(), compatibility.clone()));
     app.connect_shutdown(shutdown);
     app.run();
 }
@@ -57,31 +127,79 @@ fn load_css() {
     );
 }
 
-fn build_ui(app: &Application) {
-    let window = ReSetWindow::new(app);
+/// The open main window, the active window may also be the quick settings.
+fn main_window(app: &Application) -> Option<ReSetWindow> {
+    app.windows()
+        .into_iter()
+        .find_map(|window| window.downcast::<ReSetWindow>().ok())
+}
+
+fn build_ui(app: &Application, daemon_ready: Arc<AtomicBool>, compatibility: DaemonCompatibility) {
+    if let Some(window) = main_window(app) {
+        window.present();
+        return;
+    }
+    let window = ReSetWindow::new(app, daemon_ready, compatibility, load_window_state());
     window.present();
 }
 
+// runs in the primary instance, a second invocation of ReSet forwards its arguments here
+fn handle_command_line(
+    app: &Application,
+    command_line: &ApplicationCommandLine,
+    daemon_ready: &AtomicBool,
+    compatibility: &DaemonCompatibility,
+) -> i32 {
+    let arguments: Vec<String> = command_line
+        .arguments()
+        .iter()
+        .map(|argument| argument.to_string_lossy().to_string())
+        .collect();
+    // the quick settings are shown on their own, without activating the main window
+    if is_quick_argument(&arguments) {
+        if !daemon_ready.load(Ordering::SeqCst) {
+            command_line.printerr_literal("ReSet daemon unavailable\n");
+            return 1;
+        }
+        show_quick_settings(app, compatibility);
+        return 0;
+    }
+    let page = match parse_page_argument(&arguments) {
+        Ok(page) => page,
+        Err(error) => {
+            // printed by the invoking instance, which may not be the primary one
+            command_line.printerr_literal(&format!("{}\n", error));
+            return 1;
+        }
+    };
+    app.activate();
+    if let Some(position) = page {
+        if let Some(window) = main_window(app) {
+            window.navigate_to(position);
+        }
+    }
+    0
+}
+
 fn shutdown(_: &Application) {
-    thread::spawn(|| {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(100));
-        let res: Result<(), Error> = proxy.method_call(BASE, "UnregisterClient", ("ReSet",));
-        res
-    });
+    save_window_state();
+    shutdown_plugins();
+    remove_reload_copies(&reload_directory());
+    thread::spawn(unregister_client);
 }
 
 async fn daemon_check(ready: Arc<AtomicBool>) {
-    let handle = thread::spawn(move || {
-        let conn = Connection::new_session().unwrap();
-        let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(100));
-        let res: Result<(), Error> = proxy.method_call(BASE, "RegisterClient", ("ReSet",));
-        res
-    });
+    let handle = thread::spawn(register_client);
     let res = handle.join();
-    if res.unwrap().is_err() {
-        run_daemon(Some(ready)).await;
-    } else {
-        ready.store(true, Ordering::SeqCst);
+    if res.unwrap().is_ok() {
+        mark_daemon_ready(&ready);
+        return;
+    }
+    let daemon = run_daemon(Some(ready.clone()));
+    tokio::pin!(daemon);
+    tokio::select! {
+        _ = &mut daemon => return,
+        _ = watch_embedded_daemon(&ready) => (),
     }
+    daemon.await;
 }
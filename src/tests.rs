@@ -1,24 +1,1099 @@
+/// Starts the daemon once for all tests that need it, the runtime is kept alive until the end.
+#[cfg(test)]
+async fn start_test_daemon() -> bool {
+    use crate::components::daemon::readiness::{daemon_timeout, wait_for_daemon};
+    use crate::daemon_check;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::{Arc, OnceLock};
+    static DAEMON: OnceLock<(tokio::runtime::Runtime, Arc<AtomicBool>)> = OnceLock::new();
+    let (_, ready) = DAEMON.get_or_init(|| {
+        let ready = Arc::new(AtomicBool::new(false));
+        let rc = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        rc.spawn(daemon_check(ready.clone()));
+        (rc, ready)
+    });
+    wait_for_daemon(ready, daemon_timeout()).await
+}
+
+/// Builds the plugin in test_plugin and returns the path of its library.
+#[cfg(test)]
+fn build_test_plugin() -> std::path::PathBuf {
+    use std::path::Path;
+    use std::process::Command;
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_plugin/Cargo.toml");
+    let built = Command::new(option_env!("CARGO").unwrap_or("cargo"))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .status()
+        .expect("Failed to run cargo");
+    assert!(built.success(), "test plugin could not be built");
+    manifest.with_file_name("target/debug/libbetter_test_plugin.so")
+}
+
 #[tokio::test]
 #[cfg(test)]
 async fn test_plugins() {
-    use crate::daemon_check;
+    use crate::components::plugin::abi::check_plugin_abi;
+    use crate::components::plugin::manager::{call_plugin, panic_taker, plugin_library};
     use re_set_lib::utils::plugin::plugin_tests;
     use re_set_lib::utils::plugin_setup::FRONTEND_PLUGINS;
-    use std::hint;
-    use std::sync::atomic::AtomicBool;
-    use std::sync::Arc;
-    let ready = Arc::new(AtomicBool::new(false));
-    let rc = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-    rc.spawn(daemon_check(ready.clone()));
-    while !ready.load(std::sync::atomic::Ordering::SeqCst) {
-        hint::spin_loop();
-    }
+    assert!(
+        start_test_daemon().await,
+        "ReSet daemon did not become ready"
+    );
+    let mut failed = Vec::new();
     unsafe {
         for plugin in FRONTEND_PLUGINS.iter() {
-            let name = (plugin.frontend_name)();
-            let tests = (plugin.frontend_tests)();
-            plugin_tests(name, tests);
+            let library = plugin_library(plugin);
+            let abi = library
+                .as_deref()
+                .ok_or_else(|| String::from("could not find the plugin library"))
+                .and_then(check_plugin_abi);
+            if let Err(message) = abi {
+                failed.push(message);
+                continue;
+            }
+            // a crashing plugin must not prevent the tests of the others
+            let take_panic = library.as_deref().and_then(panic_taker);
+            let tests = call_plugin("frontend_name", take_panic, || (plugin.frontend_name)())
+                .and_then(|name| {
+                    call_plugin("frontend_tests", take_panic, || (plugin.frontend_tests)())
+                        .map(|tests| (name, tests))
+                });
+            match tests {
+                Ok((name, tests)) => plugin_tests(name, tests),
+                Err(error) => failed.push(error.to_string()),
+            }
         }
     }
-    rc.shutdown_background();
+    assert!(
+        failed.is_empty(),
+        "Plugins could not be tested: {:?}",
+        failed
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn test_page_argument() {
+    use crate::cli::page::{is_quick_argument, parse_page_argument, PageArgumentError};
+    use crate::components::base::utils::Position;
+    let arguments =
+        |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+    assert_eq!(parse_page_argument(&arguments(&["ReSet"])), Ok(None));
+    assert_eq!(
+        parse_page_argument(&arguments(&["ReSet", "--page", "wifi"])),
+        Ok(Some(Position::Wifi))
+    );
+    assert_eq!(
+        parse_page_argument(&arguments(&["ReSet", "--page=Bluetooth"])),
+        Ok(Some(Position::Bluetooth))
+    );
+    assert_eq!(
+        parse_page_argument(&arguments(&["ReSet", "--page", "custom:Monitors"])),
+        Ok(Some(Position::Custom(String::from("Monitors"))))
+    );
+    assert_eq!(
+        parse_page_argument(&arguments(&["ReSet", "--page"])),
+        Err(PageArgumentError::MissingValue)
+    );
+    assert_eq!(
+        parse_page_argument(&arguments(&["ReSet", "--page", "nothing"])),
+        Err(PageArgumentError::UnknownPage(String::from("nothing")))
+    );
+    assert!(is_quick_argument(&arguments(&["ReSet", "--quick"])));
+    assert!(!is_quick_argument(&arguments(&[
+        "--quick", "--page", "wifi"
+    ])));
+}
+
+#[test]
+#[cfg(test)]
+fn test_volume_argument() {
+    use crate::cli::commands::{parse_volume, MAX_VOLUME, NORMAL_VOLUME};
+    assert_eq!(parse_volume("50", 0).unwrap(), NORMAL_VOLUME / 2);
+    assert_eq!(parse_volume("100%", 0).unwrap(), NORMAL_VOLUME);
+    assert_eq!(
+        parse_volume("+50", NORMAL_VOLUME / 2).unwrap(),
+        NORMAL_VOLUME
+    );
+    assert_eq!(parse_volume("-100", NORMAL_VOLUME / 2).unwrap(), 0);
+    assert_eq!(parse_volume("500", 0).unwrap(), MAX_VOLUME);
+    assert!(parse_volume("loud", 0).is_err());
+}
+
+#[test]
+#[cfg(test)]
+fn test_status_json() {
+    use crate::cli::commands::NORMAL_VOLUME;
+    use crate::cli::status::{
+        audio_object_json, audio_stream_json, bluetooth_device_json, event_json, path_json,
+        removed_event_json, wifi_status_json,
+    };
+    use dbus::Path;
+    use re_set_lib::audio::audio_structures::{InputStream, Sink};
+    use re_set_lib::bluetooth::bluetooth_structures::BluetoothDevice;
+    use re_set_lib::network::network_structures::{AccessPoint, WifiDevice};
+    use re_set_lib::signals::SinkRemoved;
+    use serde_json::json;
+
+    let sink = Sink {
+        index: 1,
+        name: String::from("sink"),
+        alias: String::from("Speakers"),
+        channels: 2,
+        volume: vec![NORMAL_VOLUME / 2, NORMAL_VOLUME],
+        muted: true,
+        active: 0,
+    };
+    let sink_json = audio_object_json(&sink);
+    assert_eq!(sink_json["alias"], "Speakers");
+    // the first channel is reported
+    assert_eq!(sink_json["volume"], NORMAL_VOLUME / 2);
+    assert_eq!(sink_json["volume_percent"], 50.0);
+    assert_eq!(sink_json["muted"], true);
+
+    let stream = InputStream {
+        index: 3,
+        application_name: String::from("Player"),
+        sink_index: 1,
+        corked: true,
+        ..Default::default()
+    };
+    let stream_json = audio_stream_json(&stream);
+    assert_eq!(stream_json["application_name"], "Player");
+    assert_eq!(stream_json["object_index"], 1);
+    assert_eq!(stream_json["volume"], 0);
+    assert_eq!(stream_json["corked"], true);
+
+    let device = BluetoothDevice {
+        path: Path::from("/org/bluez/hci0/dev_00"),
+        alias: String::from("Headphones"),
+        connected: true,
+        ..Default::default()
+    };
+    let device_json = bluetooth_device_json(&device);
+    assert_eq!(device_json["path"], "/org/bluez/hci0/dev_00");
+    assert_eq!(device_json["alias"], "Headphones");
+    assert_eq!(device_json["connected"], true);
+
+    let home = AccessPoint {
+        ssid: b"home".to_vec(),
+        strength: 70,
+        dbus_path: Path::from("/ap/1"),
+        ..Default::default()
+    };
+    // hidden networks have no ssid and must not match a disconnected device
+    let hidden = AccessPoint {
+        dbus_path: Path::from("/ap/2"),
+        ..Default::default()
+    };
+    let access_points = [hidden, home];
+    let mut wifi_device = WifiDevice {
+        name: String::from("wlan0"),
+        ..Default::default()
+    };
+    let disconnected = wifi_status_json(&wifi_device, &access_points);
+    assert_eq!(disconnected["device"]["name"], "wlan0");
+    assert!(disconnected["access_point"].is_null());
+    wifi_device.active_access_point = b"home".to_vec();
+    let connected = wifi_status_json(&wifi_device, &access_points);
+    assert_eq!(connected["device"]["active_access_point"], "home");
+    assert_eq!(connected["access_point"]["ssid"], "home");
+    assert_eq!(connected["access_point"]["path"], "/ap/1");
+
+    assert_eq!(
+        event_json("SinkRemoved", removed_event_json(&SinkRemoved { index: 4 })),
+        json!({ "event": "SinkRemoved", "data": { "index": 4 } })
+    );
+    assert_eq!(path_json(&Path::from("/ap/1")), json!({ "path": "/ap/1" }));
+}
+
+#[test]
+#[cfg(test)]
+fn test_daemon_compatibility() {
+    use crate::components::daemon::compat::{
+        parse_introspection, versions_compatible, DaemonCompatibility,
+    };
+    assert!(versions_compatible("2.2.0", "2.2.1"));
+    assert!(versions_compatible("2.2.0", "2.3.0"));
+    assert!(!versions_compatible("2.2.0", "2.1.9"));
+    assert!(!versions_compatible("2.2.0", "3.0.0"));
+    assert!(!versions_compatible("2.2.0", "unknown"));
+
+    let xml = r#"<node>
+  <interface name="org.Xetibo.ReSet.Audio">
+    <method name="ListSinks">
+      <arg name="sinks" type="a(ussqaubi)" direction="out"/>
+    </method>
+    <signal name="SinkAdded"/>
+  </interface>
+  <interface name="org.Xetibo.ReSet.Network">
+    <method name="ListAccessPoints"/>
+  </interface>
+</node>"#;
+    let compatibility = DaemonCompatibility {
+        version: Some(String::from("2.2.0")),
+        methods: Some(parse_introspection(xml)),
+    };
+    assert!(compatibility.supports(&[("org.Xetibo.ReSet.Audio", "ListSinks")]));
+    assert!(compatibility.supports(&[("org.Xetibo.ReSet.Network", "ListAccessPoints")]));
+    assert!(!compatibility.supports(&[("org.Xetibo.ReSet.Audio", "SinkAdded")]));
+    assert!(!compatibility.supports(&[("org.Xetibo.ReSet.Audio", "ListAccessPoints")]));
+    assert!(DaemonCompatibility::default().supports(&[("org.Xetibo.ReSet.Audio", "ListCards")]));
+}
+
+#[test]
+#[cfg(test)]
+fn test_member_signatures() {
+    use crate::components::daemon::proxy::{
+        audio::SET_SINK_MUTE, network::CONNECT_TO_NEW_ACCESS_POINT, parse_member_signatures,
+        MemberSignature, TupleSignature,
+    };
+    assert_eq!(<() as TupleSignature>::signature(), "");
+    assert_eq!(<(u32, bool) as TupleSignature>::signature(), "ub");
+    assert_eq!(<(Vec<String>,) as TupleSignature>::signature(), "as");
+
+    let xml = r#"<node>
+  <interface name="org.Xetibo.ReSet.Audio">
+    <method name="SetSinkMute">
+      <arg name="index" type="u" direction="in"/>
+      <arg name="muted" type="b"/>
+    </method>
+    <method name="ListCards"/>
+    <signal name="SinkRemoved">
+      <arg name="index" type="u"/>
+    </signal>
+  </interface>
+</node>"#;
+    let members = parse_member_signatures(xml);
+    let member = |name: &str, input: &str, output: &str| MemberSignature {
+        interface: String::from("org.Xetibo.ReSet.Audio"),
+        name: String::from(name),
+        input: String::from(input),
+        output: String::from(output),
+    };
+    assert_eq!(
+        members,
+        vec![
+            member("SetSinkMute", "ub", ""),
+            member("ListCards", "", ""),
+            member("SinkRemoved", "", "u"),
+        ]
+    );
+    assert_eq!(members[0], SET_SINK_MUTE.signature());
+    assert_eq!(CONNECT_TO_NEW_ACCESS_POINT.signature().input, "(ayyoob)s");
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_daemon_signatures() {
+    use crate::components::daemon::compat::INTROSPECT;
+    use crate::components::daemon::proxy::{
+        method_signatures, parse_member_signatures, signal_signatures, DaemonCall, DaemonClient,
+    };
+    use std::collections::HashSet;
+    assert!(
+        start_test_daemon().await,
+        "ReSet daemon did not become ready"
+    );
+    let (xml,) = DaemonClient::new()
+        .call(&INTROSPECT, ())
+        .expect("Failed to introspect the daemon");
+    let daemon = parse_member_signatures(&xml);
+    // interfaces of unavailable capabilities, e.g. missing bluetooth, are not exported
+    let interfaces: HashSet<&str> = daemon
+        .iter()
+        .map(|member| member.interface.as_str())
+        .collect();
+    for method in method_signatures() {
+        if !interfaces.contains(method.interface.as_str()) {
+            continue;
+        }
+        assert!(
+            daemon.contains(&method),
+            "{}.{} does not match the daemon",
+            method.interface,
+            method.name
+        );
+    }
+    // signals without declaration cannot be checked
+    for signal in signal_signatures() {
+        let declared = daemon
+            .iter()
+            .find(|member| member.interface == signal.interface && member.name == signal.name);
+        if let Some(declared) = declared {
+            assert_eq!(declared, &signal);
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test_frontend_config() {
+    use crate::components::base::utils::Position;
+    use crate::components::config::frontend::{FrontendConfig, NORMAL_VOLUME};
+    use std::time::Duration;
+    assert_eq!(
+        FrontendConfig::parse("").unwrap(),
+        FrontendConfig::default()
+    );
+    let config = FrontendConfig::parse(
+        r#"
+        start_page = "bluetooth"
+        hidden_entries = ["Connectivity"]
+
+        [audio]
+        volume_step = 5
+        max_volume = 100.0
+
+        [dbus]
+        timeout = 2000
+        daemon_timeout = -1
+
+        [plugins]
+        allow = ["Monitors", "Keyboard"]
+        deny = ["keyboard"]
+        hot_reload = true
+
+        [window]
+        width = 800
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.start_page, Some(Position::Bluetooth));
+    assert!(config.is_hidden("connectivity"));
+    assert!(!config.is_hidden("WiFi"));
+    assert_eq!(config.max_volume_value(), NORMAL_VOLUME);
+    assert_eq!(config.volume_step_value(), NORMAL_VOLUME / 20.0);
+    assert_eq!(config.dbus_timeout, Some(Duration::from_millis(2000)));
+    assert_eq!(config.dbus_long_timeout, None);
+    assert_eq!(config.daemon_timeout, Some(Duration::ZERO));
+    assert!(config.plugin_enabled(&["libmonitors.so", "Monitors"]));
+    assert!(!config.plugin_enabled(&["libkeyboard.so", "Keyboard"]));
+    assert!(!config.plugin_enabled(&["libother.so", "Other"]));
+    assert!(config.plugin_denied("KEYBOARD"));
+    assert!(!config.plugin_denied("libkeyboard.so"));
+    assert!(config.hot_reload_plugins);
+    assert!(!FrontendConfig::default().hot_reload_plugins);
+    // both dimensions are required
+    assert_eq!(config.window_size, None);
+    assert!(FrontendConfig::parse("hidden_entries = [").is_err());
+}
+
+#[test]
+#[cfg(test)]
+fn test_window_state() {
+    use crate::components::base::utils::Position;
+    use crate::components::config::state::WindowState;
+    let state = WindowState {
+        size: Some((900, 700)),
+        sidebar_collapsed: true,
+        position: Some(Position::Custom(String::from("Monitors"))),
+    };
+    assert_eq!(WindowState::parse(&state.to_toml()).unwrap(), state);
+    assert_eq!(
+        WindowState::parse(&WindowState::default().to_toml()).unwrap(),
+        WindowState::default()
+    );
+    for position in [
+        Position::Home,
+        Position::Connectivity,
+        Position::Wifi,
+        Position::Bluetooth,
+        Position::Audio,
+        Position::AudioOutput,
+        Position::AudioInput,
+        Position::Plugins,
+    ] {
+        let page = position.page_name();
+        assert_eq!(Position::from_page_name(&page), Some(position));
+    }
+    let state = WindowState::parse("width = -1\nheight = 700\npage = \"unknown\"").unwrap();
+    assert_eq!(state.size, None);
+    assert_eq!(state.position, None);
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_isolation() {
+    use crate::components::plugin::manager::{call_plugin, validate_plugin_data, PluginError};
+    use re_set_lib::utils::plugin::SidebarInfo;
+    use reset_plugin_api::entry::{catch_panic, take_panic, PanicTaker};
+    // what frontend_entries! expands to, without the export
+    let take_panic: Option<PanicTaker> = Some(take_panic);
+    assert_eq!(
+        call_plugin("frontend_name", take_panic, || {
+            catch_panic(|| String::from("Monitors"))
+        }),
+        Ok(String::from("Monitors"))
+    );
+    assert_eq!(
+        call_plugin("frontend_startup", take_panic, || {
+            catch_panic(|| panic!("startup failed"))
+        }),
+        Err::<(), _>(PluginError::new("frontend_startup", "startup failed"))
+    );
+    let error = call_plugin("frontend_data", take_panic, || {
+        catch_panic(|| -> (SidebarInfo, Vec<gtk::Box>) { panic!("{} widgets", 0) })
+    })
+    .unwrap_err();
+    assert_eq!(error.message, "0 widgets");
+    // the panic is only reported once
+    assert_eq!(
+        call_plugin("frontend_shutdown", take_panic, || catch_panic(|| ())),
+        Ok(())
+    );
+
+    let sidebar_info = |name: &'static str| SidebarInfo {
+        name,
+        icon_name: "",
+        parent: None,
+    };
+    let empty = validate_plugin_data(&sidebar_info(" "), &[], &[]).unwrap_err();
+    assert_eq!(empty.message, "returned an empty sidebar name");
+    let taken = validate_plugin_data(&sidebar_info("Audio"), &[], &["WiFi", "Audio"]).unwrap_err();
+    assert_eq!(taken.message, "sidebar name Audio is already in use");
+    let no_widgets = validate_plugin_data(&sidebar_info("Monitors"), &[], &["Audio"]).unwrap_err();
+    assert_eq!(no_widgets.message, "returned no widgets");
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_panic() {
+    use crate::components::plugin::abi::check_plugin_abi;
+    use crate::components::plugin::manager::{call_plugin, panic_taker, PluginError};
+    // the panic has to be caught by the std of the plugin, not the one of the tests
+    let path = build_test_plugin();
+    unsafe {
+        let library = libloading::Library::new(&path).expect("Failed to load the test plugin");
+        assert_eq!(check_plugin_abi(&path), Ok(()));
+        let take_panic = panic_taker(&path);
+        assert!(take_panic.is_some());
+
+        let frontend_panic = library
+            .get::<unsafe extern "C" fn() -> String>(b"frontend_panic")
+            .unwrap();
+        assert_eq!(
+            call_plugin("frontend_panic", take_panic, || frontend_panic()),
+            Err(PluginError::new("frontend_panic", "frontend panic called"))
+        );
+        // the plugin keeps working after a panic
+        let frontend_startup = library
+            .get::<unsafe extern "C" fn()>(b"frontend_startup")
+            .unwrap();
+        assert_eq!(
+            call_plugin("frontend_startup", take_panic, || frontend_startup()),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_abi() {
+    use crate::components::plugin::abi::{
+        PluginAbi, DBUS_VERSION, GTK_VERSION, RE_SET_LIB_VERSION,
+    };
+    let host = PluginAbi::current();
+    let plugin =
+        PluginAbi::parse("abi=4;re_set-lib=5.2.0;gtk4=0.8.3;dbus=0.9.7;rustc=1.77.0").unwrap();
+    assert_eq!(plugin.check(&host), Ok(()));
+    // plugins of ABI 1 let their panics unwind into ReSet as well
+    let plugin = PluginAbi::parse("abi=1;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7").unwrap();
+    assert!(plugin.check(&host).is_err());
+    // plugins of ABI 2 declare their own PluginHost without version and size
+    let plugin = PluginAbi::parse("abi=2;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7").unwrap();
+    assert!(plugin.check(&host).is_err());
+    // plugins of ABI 3 let their panics unwind into ReSet
+    let plugin = PluginAbi::parse("abi=3;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7").unwrap();
+    assert!(plugin.check(&host).is_err());
+    let plugin = PluginAbi::parse("abi=4;re_set-lib=4.9.0;gtk4=0.8.1;dbus=0.9.7").unwrap();
+    assert_eq!(
+        plugin.check(&host),
+        Err(String::from(
+            "built against re_set-lib 4.9.0, ReSet uses 5.2.5"
+        ))
+    );
+    let plugin = PluginAbi::parse("abi=4;re_set-lib=5.2.5;gtk4=0.7.3;dbus=0.9.7").unwrap();
+    assert!(plugin.check(&host).is_err());
+    assert!(PluginAbi::parse("abi=4;gtk4=0.8.1;dbus=0.9.7").is_err());
+    // the PluginHost passes the dbus connection of ReSet
+    let plugin = PluginAbi::parse("abi=4;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.8.0").unwrap();
+    assert_eq!(
+        plugin.check(&host),
+        Err(String::from("built against dbus 0.8.0, ReSet uses 0.9.7"))
+    );
+    assert!(PluginAbi::parse("abi=4;re_set-lib=5.2.5;gtk4=0.8.1").is_err());
+    assert!(PluginAbi::parse("abi=one;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7").is_err());
+
+    // the declared versions have to follow the dependencies
+    let manifest = include_str!("../Cargo.toml")
+        .parse::<toml::Table>()
+        .unwrap();
+    let dependencies = manifest["dependencies"].as_table().unwrap();
+    assert_eq!(
+        dependencies["re_set-lib"].as_str(),
+        Some(RE_SET_LIB_VERSION)
+    );
+    assert_eq!(dependencies["gtk"]["version"].as_str(), Some(GTK_VERSION));
+    assert_eq!(dependencies["dbus"].as_str(), Some(DBUS_VERSION));
+    let api_manifest = include_str!("../plugin_api/Cargo.toml")
+        .parse::<toml::Table>()
+        .unwrap();
+    assert_eq!(
+        api_manifest["dependencies"]["dbus"].as_str(),
+        Some(DBUS_VERSION)
+    );
+    assert_eq!(
+        dependencies["reset_plugin_api"]["version"].as_str(),
+        api_manifest["package"]["version"].as_str()
+    );
+
+    // the test plugin has to keep loading
+    let manifest = include_str!("../test_plugin/Cargo.toml")
+        .parse::<toml::Table>()
+        .unwrap();
+    assert_eq!(
+        manifest["dependencies"]["re_set-lib"].as_str(),
+        Some(RE_SET_LIB_VERSION)
+    );
+    let declaration = include_str!("../test_plugin/src/lib.rs")
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("c\"abi="))
+        .and_then(|declaration| declaration.split('"').next())
+        .unwrap();
+    let plugin = PluginAbi::parse(&format!("abi={}", declaration)).unwrap();
+    assert_eq!(plugin.check(&host), Ok(()));
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_hooks() {
+    use crate::components::base::utils::Listeners;
+    use crate::components::plugin::manager::PluginHooks;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static ENTERED: AtomicUsize = AtomicUsize::new(0);
+    static LEFT: AtomicUsize = AtomicUsize::new(0);
+    unsafe extern "C" fn enter() {
+        ENTERED.fetch_add(1, Ordering::SeqCst);
+    }
+    unsafe extern "C" fn leave() {
+        LEFT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let listeners = Listeners::default();
+    listeners.enter_plugin_page(PluginHooks {
+        enter: Some(enter),
+        leave: Some(leave),
+        take_panic: None,
+    });
+    assert_eq!(ENTERED.load(Ordering::SeqCst), 1);
+    assert_eq!(LEFT.load(Ordering::SeqCst), 0);
+    listeners.leave_plugin_page();
+    assert_eq!(LEFT.load(Ordering::SeqCst), 1);
+    // leaving a page that is not a plugin page does nothing
+    listeners.leave_plugin_page();
+    assert_eq!(LEFT.load(Ordering::SeqCst), 1);
+    // plugins without hooks can still be entered
+    listeners.enter_plugin_page(PluginHooks::default());
+    listeners.leave_plugin_page();
+    assert_eq!(ENTERED.load(Ordering::SeqCst), 1);
+    assert_eq!(LEFT.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[cfg(test)]
+fn test_page_extensions() {
+    use crate::components::plugin::extension::{insertion_order, ExtensionPoint};
+    assert_eq!(
+        ExtensionPoint::from_name("sink_box"),
+        Some(ExtensionPoint::SinkBox)
+    );
+    assert_eq!(
+        ExtensionPoint::from_name("connectivity_page"),
+        Some(ExtensionPoint::ConnectivityPage)
+    );
+    assert_eq!(ExtensionPoint::from_name("Sink_Box"), None);
+    assert_eq!(ExtensionPoint::from_name("monitor_page"), None);
+
+    // positions refer to the built-in content, appended extensions keep their order
+    assert_eq!(
+        insertion_order(&[2, -1, 1, -1, 0]),
+        vec![(4, 0), (2, 2), (0, 4), (1, -1), (3, -1)]
+    );
+    assert_eq!(insertion_order(&[1, 1]), vec![(0, 1), (1, 2)]);
+    assert!(insertion_order(&[]).is_empty());
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_listeners() {
+    use crate::components::base::utils::Listeners;
+    use std::sync::atomic::Ordering;
+    let listeners = Listeners::default();
+    let first = listeners.add_plugin_listener();
+    let second = listeners.add_plugin_listener();
+    assert!(first.load(Ordering::SeqCst) && second.load(Ordering::SeqCst));
+    listeners.leave_plugin_page();
+    assert!(!first.load(Ordering::SeqCst) && !second.load(Ordering::SeqCst));
+    // listeners of the next page are not affected by the previous one
+    let third = listeners.add_plugin_listener();
+    assert!(third.load(Ordering::SeqCst));
+}
+
+#[test]
+#[cfg(test)]
+fn test_shared_daemon_listeners() {
+    use crate::components::base::utils::Listeners;
+    let main_window = Listeners::default();
+    let quick_settings = Listeners::default();
+    assert!(main_window.acquire_network_listener());
+    assert!(!main_window.acquire_network_listener());
+    assert!(quick_settings.acquire_network_listener());
+    // closing the quick settings keeps the listener of the main window
+    assert!(!quick_settings.release_network_listener());
+    assert!(!quick_settings.release_network_listener());
+    assert!(main_window.release_network_listener());
+
+    assert!(main_window.acquire_bluetooth_listener());
+    assert!(!main_window.acquire_bluetooth_listener());
+    assert!(quick_settings.acquire_bluetooth_listener());
+    assert!(!quick_settings.release_bluetooth_listener());
+    assert!(main_window.release_bluetooth_listener());
+    // a loop stopping twice does not release the listener of another window
+    assert!(!main_window.release_bluetooth_listener());
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_host() {
+    use crate::components::plugin::host::PLUGIN_HOST;
+    use reset_plugin_api::{PluginHost, PLUGIN_HOST_VERSION};
+    use std::any::Any;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    assert_eq!(PLUGIN_HOST.check(), Ok(()));
+    let newer = PluginHost {
+        version: PLUGIN_HOST_VERSION + 1,
+        ..PLUGIN_HOST
+    };
+    assert!(newer.check().is_err());
+    let larger = PluginHost {
+        size: PLUGIN_HOST.size + 8,
+        ..PLUGIN_HOST
+    };
+    assert!(larger.check().is_err());
+
+    let work = || -> Box<dyn Any + Send> { Box::new(42) };
+    // only the thread running the main loop may pass on the update
+    assert!((PLUGIN_HOST.spawn_blocking)(Box::new(work), Box::new(|_| ())).is_err());
+    let context = glib::MainContext::default();
+    let _owner = context.acquire().unwrap();
+    let result = Rc::new(Cell::new(0));
+    let update_result = result.clone();
+    let spawned = (PLUGIN_HOST.spawn_blocking)(
+        Box::new(work),
+        Box::new(move |value| update_result.set(*value.downcast::<i32>().unwrap())),
+    );
+    assert_eq!(spawned, Ok(()));
+    while result.get() == 0 {
+        context.iteration(true);
+    }
+    assert_eq!(result.get(), 42);
+    let from_thread = thread::spawn(move || {
+        (PLUGIN_HOST.spawn_blocking)(Box::new(work), Box::new(|_| ())).is_err()
+    });
+    assert!(from_thread.join().unwrap());
+
+    let invoked = Arc::new(AtomicBool::new(false));
+    let invoked_thread = invoked.clone();
+    thread::spawn(move || {
+        (PLUGIN_HOST.invoke_on_main)(Box::new(move || {
+            invoked_thread.store(true, Ordering::SeqCst)
+        }))
+    })
+    .join()
+    .unwrap();
+    while !invoked.load(Ordering::SeqCst) {
+        context.iteration(true);
+    }
+
+    // without a window there is no page the listener could belong to
+    let started = Arc::new(AtomicBool::new(false));
+    let listener_started = started.clone();
+    (PLUGIN_HOST.register_listener)(Box::new(move |_| {
+        listener_started.store(true, Ordering::SeqCst)
+    }));
+    while context.iteration(false) {}
+    assert!(!started.load(Ordering::SeqCst));
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_reload() {
+    use crate::cli::plugin_tests::{run_plugin_abi_check, CHECK_PLUGIN_ABI_FLAG};
+    use crate::components::plugin::reload::{plugin_source, remove_reload_copies, replace_plugin};
+    use std::fs;
+    use std::path::PathBuf;
+
+    let installed = PathBuf::from("/usr/lib/reset/libreset_monitors.so");
+    let build = PathBuf::from("/home/user/monitors/target/debug/libreset_monitors.so");
+    let reloaded = vec![(String::from("libreset_monitors.so"), build.clone())];
+    assert_eq!(
+        plugin_source("libreset_monitors.so", installed.clone(), &reloaded),
+        build
+    );
+    assert_eq!(
+        plugin_source("libreset_keyboard.so", installed.clone(), &reloaded),
+        installed
+    );
+
+    assert_eq!(
+        replace_plugin(&mut [], "libreset_unknown.so", &build),
+        Err(String::from(
+            "libreset_unknown.so is not a loaded frontend plugin"
+        ))
+    );
+    // new builds are checked by loading them in a separate process
+    let check = |library: &str| {
+        run_plugin_abi_check(&["ReSet", CHECK_PLUGIN_ABI_FLAG, library].map(String::from))
+            .map_err(|error| error.to_string())
+    };
+    let test_plugin = build_test_plugin();
+    assert_eq!(check(test_plugin.to_str().unwrap()), Ok(()));
+    assert!(check("/nonexistent/libreset_monitors.so").is_err());
+
+    let directory = std::env::temp_dir().join(format!("reset-test-reload-{}", std::process::id()));
+    let copy = directory.join("0");
+    fs::create_dir_all(&copy).unwrap();
+    fs::write(copy.join("libreset_monitors.so"), b"").unwrap();
+    remove_reload_copies(&directory);
+    assert!(!directory.exists());
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_manifest() {
+    use crate::components::plugin::manifest::{PluginManifest, RowKind, DEFAULT_MANIFEST_ICON};
+    use crate::components::utils::BASE;
+    let manifest = PluginManifest::from_toml(
+        r#"
+        name = "Night light"
+        capabilities = ["Brightness"]
+
+        [dbus]
+        path = "/org/Xetibo/ReSet/Brightness"
+        interface = "org.Xetibo.ReSet.Brightness"
+
+        [[rows]]
+        type = "switch"
+        title = "Enabled"
+        property = "NightLight"
+
+        [[rows]]
+        type = "slider"
+        title = "Temperature"
+        getter = "GetTemperature"
+        setter = "SetTemperature"
+        min = 1000
+        max = 6500
+        step = 100
+        signature = "u"
+
+        [[rows]]
+        type = "button"
+        title = "Reset"
+        method = "Reset"
+        bus_name = "org.example.Other"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(manifest.name, "Night light");
+    assert_eq!(manifest.icon_name, DEFAULT_MANIFEST_ICON);
+    assert_eq!(manifest.parent, None);
+    assert_eq!(manifest.capabilities, vec![String::from("Brightness")]);
+    assert_eq!(manifest.rows.len(), 3);
+    assert_eq!(manifest.rows[0].kind, RowKind::Switch);
+    assert_eq!(manifest.rows[0].target.bus_name, BASE);
+    assert_eq!(
+        manifest.rows[1].kind,
+        RowKind::Slider {
+            min: 1000.0,
+            max: 6500.0,
+            step: 100.0
+        }
+    );
+    assert_eq!(manifest.rows[1].signature, "u");
+    assert!(!manifest.rows[2].readable());
+    assert_eq!(manifest.rows[2].target.bus_name, "org.example.Other");
+
+    let error = |content: &str| PluginManifest::from_toml(content).unwrap_err();
+    assert_eq!(error("rows = []"), "missing name");
+    assert_eq!(error("name = \"Test\"\nrows = []"), "rows is empty");
+    let row = |row: &str| {
+        error(&format!(
+            "name = \"Test\"\n[dbus]\npath = \"/test\"\ninterface = \"org.example.Test\"\n[[rows]]\ntitle = \"Row\"\n{}",
+            row
+        ))
+    };
+    assert_eq!(row("type = \"button\""), "row 1: button without method");
+    assert_eq!(row("type = \"toggle\""), "row 1: unknown row type toggle");
+    assert_eq!(
+        row("type = \"switch\"\nproperty = \"Not a member\""),
+        "row 1: invalid property Not a member"
+    );
+    assert_eq!(
+        row("type = \"slider\"\nproperty = \"Value\"\nmin = 5\nmax = 1"),
+        "row 1: invalid slider range"
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn test_search_keywords() {
+    use crate::components::plugin::search::SearchKeyword;
+    use crate::components::window::search::{entry_matches, matching_row};
+    let keywords = vec![
+        SearchKeyword::new("wireless", None),
+        SearchKeyword::new("password", Some("Saved Networks")),
+        SearchKeyword::row("Available networks"),
+    ];
+    assert!(entry_matches("WiFi", &keywords, "wifi"));
+    assert!(entry_matches("WiFi", &keywords, "Wireless"));
+    assert!(entry_matches("WiFi", &keywords, "avail"));
+    assert!(!entry_matches("WiFi", &keywords, "equalizer"));
+
+    // pages found by their name or by keywords without a row are not scrolled
+    assert_eq!(matching_row("WiFi", &keywords, "wifi"), None);
+    assert_eq!(matching_row("WiFi", &keywords, "wireless"), None);
+    assert_eq!(matching_row("WiFi", &keywords, ""), None);
+    assert_eq!(
+        matching_row("WiFi", &keywords, "PASS"),
+        Some("Saved Networks")
+    );
+    // the first keyword with a row is used
+    assert_eq!(
+        matching_row("WiFi", &keywords, "networks"),
+        Some("Available networks")
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn test_plugin_test_reports() {
+    use crate::cli::plugin_tests::{
+        json_report, junit_report, parse_plugin_test_arguments, PluginTestReport, ReportFormat,
+        TestOutcome, TestResult,
+    };
+    use std::time::Duration;
+    let arguments = |arguments: &[&str]| {
+        let mut command = vec![String::from("ReSet"), String::from("--test-plugins")];
+        command.extend(arguments.iter().map(|argument| argument.to_string()));
+        parse_plugin_test_arguments(&command)
+    };
+    let options = arguments(&[]).unwrap();
+    assert_eq!((options.plugin, options.format), (None, ReportFormat::Json));
+    let options = arguments(&["--junit", "Monitors"]).unwrap();
+    assert_eq!(
+        (options.plugin, options.format),
+        (Some(String::from("Monitors")), ReportFormat::JUnit)
+    );
+    assert!(arguments(&["Monitors", "Keyboard"]).is_err());
+    assert!(arguments(&["--xml"]).is_err());
+
+    let reports = vec![
+        PluginTestReport {
+            plugin: String::from("Monitors"),
+            file: String::from("libreset_monitors.so"),
+            error: None,
+            tests: vec![
+                TestResult {
+                    name: String::from("resolution"),
+                    duration: Duration::from_millis(20),
+                    outcome: TestOutcome::Passed,
+                },
+                TestResult {
+                    name: String::from("scale"),
+                    duration: Duration::from_millis(5),
+                    outcome: TestOutcome::Failed(String::from("expected <2>")),
+                },
+                TestResult {
+                    name: String::from("rotation"),
+                    duration: Duration::from_millis(1),
+                    outcome: TestOutcome::Panicked(String::from("index out of bounds")),
+                },
+            ],
+        },
+        PluginTestReport {
+            plugin: String::from("libbroken.so"),
+            file: String::from("libbroken.so"),
+            error: Some(String::from("incompatible gtk version")),
+            tests: Vec::new(),
+        },
+    ];
+    assert!(!reports[0].successful() && !reports[1].successful());
+
+    let json = json_report(&reports);
+    assert_eq!(json["passed"], 1);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["panicked"], 1);
+    assert_eq!(json["errors"], 1);
+    assert_eq!(json["plugins"][0]["tests"][1]["outcome"], "failed");
+    assert_eq!(json["plugins"][0]["tests"][1]["message"], "expected <2>");
+    assert_eq!(json["plugins"][1]["error"], "incompatible gtk version");
+
+    let junit = junit_report(&reports);
+    assert!(junit.starts_with("<?xml"));
+    assert!(junit.contains(
+        "<testsuites name=\"ReSet plugins\" tests=\"4\" failures=\"1\" errors=\"2\" time=\"0.026\">"
+    ));
+    assert!(junit.contains("<failure message=\"expected &lt;2&gt;\"/>"));
+    assert!(junit.contains("<error type=\"panic\" message=\"index out of bounds\"/>"));
+    assert!(junit.contains("<error type=\"load\" message=\"incompatible gtk version\"/>"));
+    assert_eq!(junit.matches("<testcase ").count(), 4);
+}
+
+#[test]
+#[cfg(test)]
+fn test_wired_status() {
+    use crate::components::home::home_page::{wired_status_in, WiredStatus};
+    use std::fs;
+    let root = std::env::temp_dir().join(format!("reset-net-{}", std::process::id()));
+    let interface = |name: &str, interface_type: &str, device: bool, carrier: Option<&str>| {
+        let path = root.join(name);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("type"), interface_type).unwrap();
+        if device {
+            fs::create_dir_all(path.join("device")).unwrap();
+        }
+        if let Some(carrier) = carrier {
+            fs::write(path.join("carrier"), carrier).unwrap();
+        }
+        path
+    };
+    assert_eq!(wired_status_in(&root), WiredStatus::Unavailable);
+    interface("lo", "772\n", false, Some("1\n"));
+    interface("br0", "1\n", false, Some("1\n"));
+    let wlan = interface("wlan0", "1\n", true, Some("1\n"));
+    fs::create_dir_all(wlan.join("wireless")).unwrap();
+    assert_eq!(wired_status_in(&root), WiredStatus::Unavailable);
+    interface("enp3s0", "1\n", true, None);
+    assert_eq!(wired_status_in(&root), WiredStatus::Disconnected);
+    interface("enp4s0", "1\n", true, Some("1\n"));
+    assert_eq!(
+        wired_status_in(&root),
+        WiredStatus::Connected(String::from("enp4s0"))
+    );
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(test)]
+fn test_navigation_history() {
+    use crate::components::base::utils::Position;
+    use crate::components::window::history::{HistoryEntry, NavigationHistory};
+    let entry = |position: Position, pages: &[&str]| HistoryEntry {
+        position,
+        pages: vec![pages.iter().map(|page| page.to_string()).collect()],
+    };
+    let mut history = NavigationHistory::default();
+    history.record(entry(Position::Home, &[]));
+    history.record(entry(Position::AudioOutput, &["audiostreams"]));
+    history.record(entry(Position::AudioOutput, &["audiostreams", "devices"]));
+    assert!(history.can_go_back() && !history.can_go_forward());
+
+    // leaving a sub page through its own back button moves back in the history
+    history.record(entry(Position::AudioOutput, &["audiostreams"]));
+    assert!(history.can_go_forward());
+    assert_eq!(
+        history.go_forward(),
+        Some(entry(Position::AudioOutput, &["audiostreams", "devices"]))
+    );
+    assert_eq!(
+        history.go_back(),
+        Some(entry(Position::AudioOutput, &["audiostreams"]))
+    );
+    assert_eq!(history.go_back(), Some(entry(Position::Home, &[])));
+    assert_eq!(history.go_back(), None);
+
+    // visiting another page drops the entries ahead
+    history.record(entry(Position::Wifi, &["main"]));
+    assert!(!history.can_go_forward());
+    assert_eq!(history.go_back(), Some(entry(Position::Home, &[])));
+    assert_eq!(history.go_forward(), Some(entry(Position::Wifi, &["main"])));
+
+    // opening a page that is already in the history adds a new entry
+    history.record(entry(Position::Home, &[]));
+    assert_eq!(history.go_back(), Some(entry(Position::Wifi, &["main"])));
+}
+
+#[test]
+#[cfg(test)]
+fn test_event_log() {
+    use crate::components::base::event_log::{
+        connect_event_logged, disconnect_event_logged, event_log_text, logged_events,
+        record_daemon_error, LoggedEvent,
+    };
+    use std::rc::Rc;
+    let event = LoggedEvent {
+        timestamp: 0,
+        method: String::from("org.Xetibo.ReSet.Audio.SetSinkVolume"),
+        message: String::from("No such sink"),
+    };
+    assert_eq!(event.method_name(), "SetSinkVolume");
+    assert!(event
+        .to_line()
+        .ends_with("] org.Xetibo.ReSet.Audio.SetSinkVolume: No such sink"));
+
+    record_daemon_error("org.Xetibo.ReSet.Test.First", "first error");
+    record_daemon_error("org.Xetibo.ReSet.Test.Second", "second error");
+    let events: Vec<LoggedEvent> = logged_events()
+        .into_iter()
+        .filter(|event| event.method.starts_with("org.Xetibo.ReSet.Test."))
+        .collect();
+    assert_eq!(events.len(), 2);
+    // the newest event comes first
+    assert_eq!(events[0].method, "org.Xetibo.ReSet.Test.Second");
+    assert_eq!(events[1].message, "first error");
+    let text = event_log_text(&events);
+    assert_eq!(text.lines().count(), 2);
+    assert!(text
+        .lines()
+        .next()
+        .unwrap()
+        .ends_with("Second: second error"));
+
+    // listeners are dropped once they are disconnected, e.g. when the window is closed
+    let window = Rc::new(());
+    let listener_window = window.clone();
+    let listener = connect_event_logged(move |_| {
+        let _ = &listener_window;
+    });
+    assert_eq!(Rc::strong_count(&window), 2);
+    disconnect_event_logged(listener);
+    assert_eq!(Rc::strong_count(&window), 1);
+}
+
+#[test]
+#[cfg(test)]
+fn test_method_status() {
+    use crate::components::daemon::proxy::network::{
+        DELETE_CONNECTION, GET_WIFI_STATUS, START_NETWORK_LISTENER,
+    };
+    use crate::components::utils::{BASE, DBUS_PATH, WIRELESS};
+    use dbus::Message;
+    let mut call = Message::new_method_call(BASE, DBUS_PATH, WIRELESS, "DeleteConnection").unwrap();
+    call.set_serial(1);
+    let reply = |result: bool| call.method_return().append1(result);
+    assert!(DELETE_CONNECTION.reports_failure(&reply(false)));
+    assert!(!DELETE_CONNECTION.reports_failure(&reply(true)));
+    // these return a value instead of whether they succeeded
+    assert!(!GET_WIFI_STATUS.reports_failure(&reply(false)));
+    assert!(!START_NETWORK_LISTENER.reports_failure(&reply(false)));
 }
@@ -1,84 +1,111 @@
-use std::sync::Arc;
+use std::any::Any;
+use std::ffi::c_char;
+use std::sync::OnceLock;
 use std::time::Duration;
 
-use dbus::blocking::Connection;
-use dbus::Error;
-use gtk::{gio, Orientation};
+use gtk::Orientation;
 use gtk::prelude::{BoxExt, ButtonExt};
 use re_set_lib::utils::plugin::{PluginCapabilities, PluginImplementation, PluginTestFunc, SidebarInfo};
+use reset_plugin_api::PluginHost;
 
 pub const BASE: &str = "org.Xetibo.ReSet.Daemon";
 pub const DBUS_PATH: &str = "/org/Xetibo/ReSet/Plugins/test";
 pub const INTERFACE: &str = "org.Xetibo.ReSet.TestPlugin";
 
-#[no_mangle]
-#[allow(improper_ctypes_definitions)]
-pub extern "C" fn capabilities() -> PluginCapabilities {
-    println!("frontend capabilities called");
-    PluginCapabilities::new(vec!["test"], PluginImplementation::Frontend)
-}
+static HOST: OnceLock<&'static PluginHost> = OnceLock::new();
 
-#[no_mangle]
-pub extern "C" fn frontend_startup() {
-    println!("frontend startup called");
-}
+reset_plugin_api::frontend_entries! {
+    // has to match the plugin ABI of ReSet and the versions in Cargo.toml
+    fn frontend_abi() -> *const c_char {
+        c"abi=4;re_set-lib=5.2.5;gtk4=0.8.1;dbus=0.9.7".as_ptr()
+    }
 
-#[no_mangle]
-pub extern "C" fn frontend_shutdown() {
-    println!("frontend shutdown called");
-}
+    fn frontend_host(host: &'static PluginHost) {
+        // none of the services may be used if ReSet was built with a different host
+        match host.check() {
+            Ok(()) => {
+                let _ = HOST.set(host);
+            }
+            Err(error) => println!("frontend host refused: {}", error),
+        }
+    }
 
-#[no_mangle]
-#[allow(improper_ctypes_definitions)]
-pub extern "C" fn frontend_data() -> (SidebarInfo, Vec<gtk::Box>) {
-    println!("frontend data called");
-    let info = SidebarInfo {
-        name: "test",
-        icon_name: "microphone-disabled-symbolic",
-        parent: None,
-    };
-    let box1 = gtk::Box::builder().orientation(Orientation::Vertical).build();
-    let box2 = gtk::Box::builder().orientation(Orientation::Horizontal).build();
-
-    let label = Arc::new(LabelWrapper {
-        label: gtk::Label::builder().label("Hello, World!").build(),
-    });
-
-    let label2 = gtk::Label::builder().label("Bye, World!").build();
-    let button = gtk::Button::builder().label("Click me!").build();
-    box1.append(&label.label);
-    box2.append(&label2);
-    box2.append(&button);
-
-    button.connect_clicked(move |_| {
-        let label = Arc::clone(&label);
-        gio::spawn_blocking(move || {
-            let conn = Connection::new_session().unwrap();
-            let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
-            let res: Result<(String, u32), Error> = proxy.method_call(INTERFACE, "Test", ());
-            let (text, age) = res.unwrap();
-            label.label.set_text(&format!("Name: {}, Age: {}", text, age));
-        });
-    });
+    fn capabilities() -> PluginCapabilities {
+        println!("frontend capabilities called");
+        PluginCapabilities::new(vec!["test"], false, PluginImplementation::Frontend)
+    }
 
-    let boxes = vec![
-        box1, box2,
-    ];
+    fn frontend_startup() {
+        println!("frontend startup called");
+    }
 
-    (info, boxes)
-}
+    fn frontend_shutdown() {
+        println!("frontend shutdown called");
+    }
 
-#[no_mangle]
-#[allow(improper_ctypes_definitions)]
-pub extern "C" fn frontend_tests() -> Vec<PluginTestFunc> {
-    println!("frontend tests called");
-    vec![]
-}
+    fn frontend_data() -> (SidebarInfo, Vec<gtk::Box>) {
+        println!("frontend data called");
+        let info = SidebarInfo {
+            name: "test",
+            icon_name: "microphone-disabled-symbolic",
+            parent: None,
+        };
+        let box1 = gtk::Box::builder().orientation(Orientation::Vertical).build();
+        let box2 = gtk::Box::builder().orientation(Orientation::Horizontal).build();
 
-pub struct LabelWrapper {
-    label: gtk::Label,
-}
+        let label = gtk::Label::builder().label("Hello, World!").build();
 
-unsafe impl Send for LabelWrapper {}
+        let label2 = gtk::Label::builder().label("Bye, World!").build();
+        let button = gtk::Button::builder().label("Click me!").build();
+        box1.append(&label);
+        box2.append(&label2);
+        box2.append(&button);
 
-unsafe impl Sync for LabelWrapper {}
+        button.connect_clicked(move |_| {
+            let host = match HOST.get() {
+                Some(host) => *host,
+                None => return,
+            };
+            let label = label.clone();
+            let spawned = (host.spawn_blocking)(
+                Box::new(move || -> Box<dyn Any + Send> {
+                    let res = (host.connection)()
+                        .and_then(|conn| {
+                            let proxy = conn.with_proxy(BASE, DBUS_PATH, Duration::from_millis(1000));
+                            proxy.method_call::<(String, u32), _, _, _>(INTERFACE, "Test", ())
+                        })
+                        .map_err(|error| error.to_string());
+                    Box::new(res)
+                }),
+                // runs on the main loop, the label can be used directly
+                Box::new(move |res| {
+                    if let Ok(res) = res.downcast::<Result<(String, u32), String>>() {
+                        match *res {
+                            Ok((text, age)) => label.set_text(&format!("Name: {}, Age: {}", text, age)),
+                            Err(error) => (host.show_error)(&error),
+                        }
+                    }
+                }),
+            );
+            if let Err(error) = spawned {
+                (host.show_error)(&error);
+            }
+        });
+
+        let boxes = vec![
+            box1, box2,
+        ];
+
+        (info, boxes)
+    }
+
+    fn frontend_tests() -> Vec<PluginTestFunc> {
+        println!("frontend tests called");
+        vec![]
+    }
+
+    // not used by ReSet, its tests call it to check that a panic stays in the plugin
+    fn frontend_panic() -> String {
+        panic!("frontend panic called")
+    }
+}